@@ -15,3 +15,13 @@ cd jet-governance
 git checkout 6b7139e
 git apply $DIRNAME/patch
 cargo build-bpf
+
+# the governance helpers need an spl-governance program of the version the crate links against
+if grep -q "^spl-governance" $DIRNAME/Cargo.toml
+then
+  cargo fetch --manifest-path $DIRNAME/Cargo.toml
+  REGISTRY=${CARGO_HOME:-$HOME/.cargo}/registry/src
+  cp -r $(ls -d $REGISTRY/*/spl-governance-2.1.4 | head -n 1) $BUILD_DIR/spl-governance
+  cd $BUILD_DIR/spl-governance
+  cargo build-bpf
+fi
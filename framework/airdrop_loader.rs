@@ -0,0 +1,353 @@
+use super::{ix, outcome::TxOutcome, tx_builder::transaction_size, Framework};
+use jet_rewards::AirdropRecipientParam;
+use poc_framework_osec::solana_sdk::{
+    message::Message, packet::PACKET_DATA_SIZE, pubkey::Pubkey, signer::Signer,
+};
+use std::{collections::BTreeMap, error::Error, fs, path::Path, str::FromStr};
+
+/// a recipient listed more than once with different amounts, left out of the load
+#[derive(Clone, Debug)]
+pub struct RecipientConflict {
+    pub recipient: Pubkey,
+    pub amounts: Vec<u64>,
+}
+
+pub struct AirdropBatch {
+    pub start_index: u64,
+    pub recipients: usize,
+    pub outcome: TxOutcome,
+}
+
+pub struct AirdropLoad {
+    /// recipients listed more than once with the same amount, loaded once
+    pub duplicates: usize,
+    pub conflicts: Vec<RecipientConflict>,
+    /// every batch sent, the last one being the first to fail if any did
+    pub batches: Vec<AirdropBatch>,
+    /// differences between the stored recipient list and the one that was loaded
+    pub mismatches: Vec<String>,
+}
+
+impl AirdropLoad {
+    pub fn is_ok(&self) -> bool {
+        self.conflicts.is_empty()
+            && self.mismatches.is_empty()
+            && self.batches.iter().all(|batch| batch.outcome.is_ok())
+    }
+
+    pub fn print(&self) {
+        outln!(
+            "airdrop load: {} batches, {} duplicates dropped",
+            self.batches.len(),
+            self.duplicates
+        );
+        for conflict in &self.conflicts {
+            outln!(
+                "  CONFLICT {} listed with amounts {:?}, left out",
+                conflict.recipient,
+                conflict.amounts
+            );
+        }
+        for batch in &self.batches {
+            if let Some(err) = batch.outcome.error() {
+                outln!(
+                    "  batch at {} with {} recipients failed: {}",
+                    batch.start_index,
+                    batch.recipients,
+                    err
+                );
+            }
+        }
+        for mismatch in &self.mismatches {
+            outln!("  MISMATCH {}", mismatch);
+        }
+    }
+}
+
+/// reads `pubkey,amount` lines, see [`parse_recipients_csv`]
+pub fn read_recipients_csv(
+    path: impl AsRef<Path>,
+) -> Result<Vec<AirdropRecipientParam>, Box<dyn Error>> {
+    parse_recipients_csv(&fs::read_to_string(path)?)
+}
+
+/// parses `pubkey,amount` lines, skipping blank lines. The first line is skipped as a header if
+/// neither field parses, a first line with only one bad field is an error like on any other line
+pub fn parse_recipients_csv(csv: &str) -> Result<Vec<AirdropRecipientParam>, Box<dyn Error>> {
+    let mut recipients = vec![];
+    for (idx, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (pubkey, amount) = line
+            .split_once(',')
+            .ok_or_else(|| format!("line {}: expected pubkey,amount", idx + 1))?;
+        let (recipient, amount) = match (
+            Pubkey::from_str(pubkey.trim()),
+            amount.trim().parse::<u64>(),
+        ) {
+            (Ok(recipient), Ok(amount)) => (recipient, amount),
+            (Err(_), Err(_)) if idx == 0 => continue,
+            (Err(err), _) => return Err(format!("line {}: {}", idx + 1, err).into()),
+            (_, Err(err)) => return Err(format!("line {}: {}", idx + 1, err).into()),
+        };
+        recipients.push(AirdropRecipientParam { recipient, amount });
+    }
+
+    Ok(recipients)
+}
+
+/// sorts recipients by pubkey as the airdrop expects, merging exact duplicates and setting
+/// aside recipients listed with different amounts. Returns the list, the number of duplicates
+/// and the conflicts
+pub fn normalize_recipients(
+    recipients: &[AirdropRecipientParam],
+) -> (Vec<AirdropRecipientParam>, usize, Vec<RecipientConflict>) {
+    let mut amounts: BTreeMap<Pubkey, Vec<u64>> = BTreeMap::new();
+    for param in recipients {
+        amounts
+            .entry(param.recipient)
+            .or_default()
+            .push(param.amount);
+    }
+
+    let mut sorted = vec![];
+    let mut duplicates = 0;
+    let mut conflicts = vec![];
+    for (recipient, listed) in amounts {
+        let mut distinct = listed.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        if distinct.len() == 1 {
+            duplicates += listed.len() - 1;
+            sorted.push(AirdropRecipientParam {
+                recipient,
+                amount: distinct[0],
+            });
+        } else {
+            conflicts.push(RecipientConflict {
+                recipient,
+                amounts: distinct,
+            });
+        }
+    }
+
+    (sorted, duplicates, conflicts)
+}
+
+/// the airdrop binary searches its recipients, so a batch can only go after the last one stored
+pub fn check_appends_in_order(
+    last: Option<&Pubkey>,
+    sorted: &[AirdropRecipientParam],
+) -> Result<(), String> {
+    match (last, sorted.first()) {
+        (Some(last), Some(first)) if first.recipient <= *last => Err(format!(
+            "recipient {} doesn't sort after the airdrop's last recipient {}, the claim lookup \
+             would miss it",
+            first.recipient, last
+        )),
+        _ => Ok(()),
+    }
+}
+
+impl Framework {
+    /// the most recipients a single `airdrop_add_recipients` transaction can carry
+    pub fn airdrop_batch_size(&self, airdrop: &Pubkey) -> usize {
+        let size = |count: usize| {
+            let recipients = (0..count)
+                .map(|_| AirdropRecipientParam {
+                    recipient: Pubkey::default(),
+                    amount: 0,
+                })
+                .collect();
+            let instructions = [ix::airdrop_add_recipients(
+                &self.pool(),
+                airdrop,
+                0,
+                recipients,
+            )];
+            transaction_size(&Message::new(
+                &instructions,
+                Some(&self.pool_authority.pubkey()),
+            ))
+        };
+        // the recipients are borsh encoded, so every one of them adds the same number of bytes
+        let (one, two) = (size(1), size(2));
+        if one > PACKET_DATA_SIZE {
+            return 0;
+        }
+
+        1 + (PACKET_DATA_SIZE - one) / (two - one)
+    }
+
+    /// sorts and dedupes `recipients`, adds them in batches that fit a transaction starting after
+    /// whatever the airdrop already holds, then reads the airdrop back to check what was stored.
+    /// Fails without sending anything if they don't all sort after the recipients already stored
+    pub fn airdrop_load(
+        &mut self,
+        airdrop: Pubkey,
+        recipients: &[AirdropRecipientParam],
+    ) -> Result<AirdropLoad, Box<dyn Error>> {
+        let (sorted, duplicates, conflicts) = normalize_recipients(recipients);
+        let state = self.airdrop_state(&airdrop)?;
+        let info = state.target_info();
+        let existing = info.recipients_total;
+        let last = existing
+            .checked_sub(1)
+            .map(|index| info.recipients[index as usize].recipient);
+        check_appends_in_order(last.as_ref(), &sorted)?;
+        let batch_size = self.airdrop_batch_size(&airdrop).max(1);
+
+        let mut batches = vec![];
+        let mut start_index = existing;
+        for batch in sorted.chunks(batch_size) {
+            let outcome = self.airdrop_add_recipients(batch.to_vec(), airdrop, start_index)?;
+            let failed = !outcome.is_ok();
+            batches.push(AirdropBatch {
+                start_index,
+                recipients: batch.len(),
+                outcome,
+            });
+            if failed {
+                break;
+            }
+            start_index += batch.len() as u64;
+        }
+
+        let mismatches = self.verify_airdrop_recipients(&airdrop, existing, &sorted)?;
+
+        Ok(AirdropLoad {
+            duplicates,
+            conflicts,
+            batches,
+            mismatches,
+        })
+    }
+
+    pub fn airdrop_load_csv(
+        &mut self,
+        airdrop: Pubkey,
+        path: impl AsRef<Path>,
+    ) -> Result<AirdropLoad, Box<dyn Error>> {
+        let recipients = read_recipients_csv(path)?;
+        self.airdrop_load(airdrop, &recipients)
+    }
+
+    /// compares the recipients stored from `start_index` on with `expected`
+    pub fn verify_airdrop_recipients(
+        &self,
+        airdrop: &Pubkey,
+        start_index: u64,
+        expected: &[AirdropRecipientParam],
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let state = self.airdrop_state(airdrop)?;
+        let info = state.target_info();
+        let mut mismatches = vec![];
+
+        let stored = info.recipients_total.saturating_sub(start_index) as usize;
+        if stored != expected.len() {
+            mismatches.push(format!(
+                "{} recipients stored after index {}, expected {}",
+                stored,
+                start_index,
+                expected.len()
+            ));
+        }
+        for (offset, param) in expected.iter().enumerate().take(stored) {
+            let index = start_index as usize + offset;
+            let target = &info.recipients[index];
+            if target.recipient != param.recipient || target.amount != param.amount {
+                mismatches.push(format!(
+                    "recipient {} is {} for {}, expected {} for {}",
+                    index, target.recipient, target.amount, param.recipient, param.amount
+                ));
+            }
+        }
+
+        Ok(mismatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipient(recipient: Pubkey, amount: u64) -> AirdropRecipientParam {
+        AirdropRecipientParam { recipient, amount }
+    }
+
+    #[test]
+    fn skips_a_header_and_blank_lines() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let path = std::env::temp_dir().join(format!("recipients-{}.csv", a));
+        fs::write(
+            &path,
+            format!("recipient,amount\n{},10\n\n  \n{},20\n", a, b),
+        )
+        .unwrap();
+
+        let recipients = read_recipients_csv(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(recipients.len(), 2);
+        assert_eq!((recipients[0].recipient, recipients[0].amount), (a, 10));
+        assert_eq!((recipients[1].recipient, recipients[1].amount), (b, 20));
+    }
+
+    #[test]
+    fn rejects_a_bad_first_line_that_isnt_a_header() {
+        let a = Pubkey::new_unique();
+        // a typo in the pubkey of a line with a valid amount isn't a header
+        let csv = format!("notapubkey,10\n{},20\n", a);
+        assert!(parse_recipients_csv(&csv).is_err());
+        // and neither is a valid pubkey with a bad amount
+        let csv = format!("{},ten\n{},20\n", a, a);
+        assert!(parse_recipients_csv(&csv).is_err());
+        // a header is only skipped on the first line
+        let csv = format!("{},10\nrecipient,amount\n", a);
+        assert!(parse_recipients_csv(&csv).is_err());
+    }
+
+    #[test]
+    fn merges_exact_duplicates_and_sets_conflicts_aside() {
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let (sorted, duplicates, conflicts) = normalize_recipients(&[
+            recipient(c, 30),
+            recipient(a, 10),
+            recipient(b, 20),
+            recipient(a, 10),
+            recipient(b, 21),
+        ]);
+
+        assert_eq!(duplicates, 1);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].recipient, b);
+        assert_eq!(conflicts[0].amounts, vec![20, 21]);
+        let loaded: Vec<(Pubkey, u64)> = sorted.iter().map(|r| (r.recipient, r.amount)).collect();
+        let mut expected = vec![(a, 10), (c, 30)];
+        expected.sort_by_key(|(recipient, _)| *recipient);
+        assert_eq!(loaded, expected);
+    }
+
+    #[test]
+    fn batches_must_sort_after_the_stored_recipients() {
+        let mut keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        keys.sort();
+        let [a, b, c] = keys;
+        let batch = [recipient(b, 10), recipient(c, 10)];
+
+        assert!(check_appends_in_order(None, &batch).is_ok());
+        assert!(check_appends_in_order(Some(&a), &batch).is_ok());
+        assert!(check_appends_in_order(Some(&b), &batch).is_err());
+        assert!(check_appends_in_order(Some(&c), &batch).is_err());
+        assert!(check_appends_in_order(Some(&c), &[]).is_ok());
+    }
+}
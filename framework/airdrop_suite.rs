@@ -0,0 +1,265 @@
+use super::{
+    clone_keypair, ix,
+    outcome::TxOutcome,
+    runner::{run_parallel, Job},
+    Framework,
+};
+use jet_rewards::AirdropRecipientParam;
+use poc_framework_osec::{
+    solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer},
+    Environment,
+};
+use std::error::Error;
+
+/// seconds from creation until the suite's airdrops expire
+const LIFETIME: i64 = 1000;
+
+const VAULT_AMOUNT: u64 = 1000;
+const CLAIM_AMOUNT: u64 = 100;
+
+/// one transition of a scenario and whether the rewards program should accept it
+pub struct Step {
+    pub name: &'static str,
+    pub expect_ok: bool,
+    pub outcome: TxOutcome,
+}
+
+impl Step {
+    fn new(name: &'static str, expect_ok: bool, outcome: TxOutcome) -> Self {
+        Step {
+            name,
+            expect_ok,
+            outcome,
+        }
+    }
+
+    pub fn as_expected(&self) -> bool {
+        self.outcome.is_ok() == self.expect_ok
+    }
+}
+
+pub struct ScenarioResult {
+    pub scenario: &'static str,
+    pub steps: Vec<Step>,
+    /// checks on the final state, beyond the outcome of each step
+    pub violations: Vec<String>,
+}
+
+impl ScenarioResult {
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty() && self.steps.iter().all(|step| step.as_expected())
+    }
+}
+
+pub struct SuiteReport {
+    pub results: Vec<ScenarioResult>,
+}
+
+impl SuiteReport {
+    pub fn failed(&self) -> Vec<&ScenarioResult> {
+        self.results.iter().filter(|r| !r.passed()).collect()
+    }
+
+    pub fn print(&self) {
+        for result in &self.results {
+            outln!(
+                "{}: {}",
+                result.scenario,
+                if result.passed() { "ok" } else { "FAILED" }
+            );
+            for step in &result.steps {
+                let got = match step.outcome.error() {
+                    None => "ok".to_string(),
+                    Some(err) => format!("failed: {}", err),
+                };
+                outln!(
+                    "  {} {} expected to {}, {}",
+                    if step.as_expected() { " " } else { "!" },
+                    step.name,
+                    if step.expect_ok { "succeed" } else { "fail" },
+                    got
+                );
+            }
+            for violation in &result.violations {
+                outln!("  ! {}", violation);
+            }
+        }
+    }
+}
+
+/// a funded airdrop expiring `LIFETIME` seconds from now with the attacker as its only recipient
+struct Setup {
+    airdrop: Pubkey,
+    recipient: Keypair,
+    expire_at: i64,
+}
+
+fn setup(test_env: &mut Framework) -> Result<Setup, Box<dyn Error>> {
+    let recipient = clone_keypair(&test_env.attacker);
+    let pool_authority = clone_keypair(&test_env.pool_authority);
+    test_env.init_stake_pool()?;
+    test_env.create_user_auth(&recipient)?;
+    test_env.authenticate_user(&recipient)?;
+    test_env.init_stake_account(&recipient)?;
+    // receives the leftovers on close
+    test_env.mint_vault_token(&pool_authority, 0)?;
+
+    let airdrop = Keypair::new();
+    let expire_at = test_env.now() + LIFETIME;
+    test_env.create_airdrop(&airdrop, expire_at)?;
+    test_env.env.mint_tokens(
+        test_env.vault_token_mint.pubkey(),
+        &test_env.vault_mint_authority,
+        ix::reward_vault(&airdrop.pubkey()),
+        VAULT_AMOUNT,
+    );
+    test_env.airdrop_add_recipients(recipients(&recipient), airdrop.pubkey(), 0)?;
+
+    Ok(Setup {
+        airdrop: airdrop.pubkey(),
+        recipient,
+        expire_at,
+    })
+}
+
+fn recipients(recipient: &Keypair) -> Vec<AirdropRecipientParam> {
+    vec![AirdropRecipientParam {
+        recipient: recipient.pubkey(),
+        amount: CLAIM_AMOUNT,
+    }]
+}
+
+type Scenario = fn(&mut Framework, &Setup) -> Result<(Vec<Step>, Vec<String>), Box<dyn Error>>;
+
+fn scenarios() -> Vec<(&'static str, Scenario)> {
+    vec![
+        ("claim before finalize", |fw, s| {
+            let claim = fw.airdrop_claim(&s.recipient, s.airdrop)?;
+            Ok((vec![Step::new("airdrop_claim", false, claim)], vec![]))
+        }),
+        ("claim after finalize", |fw, s| {
+            let finalize = fw.airdrop_finalize(s.airdrop)?;
+            let claim = fw.airdrop_claim(&s.recipient, s.airdrop)?;
+            Ok((
+                vec![
+                    Step::new("airdrop_finalize", true, finalize),
+                    Step::new("airdrop_claim", true, claim),
+                ],
+                vec![],
+            ))
+        }),
+        ("claim twice", |fw, s| {
+            let finalize = fw.airdrop_finalize(s.airdrop)?;
+            let first = fw.airdrop_claim(&s.recipient, s.airdrop)?;
+            let second = fw.airdrop_claim(&s.recipient, s.airdrop)?;
+            Ok((
+                vec![
+                    Step::new("airdrop_finalize", true, finalize),
+                    Step::new("airdrop_claim", true, first),
+                    Step::new("airdrop_claim again", false, second),
+                ],
+                vec![],
+            ))
+        }),
+        ("add recipients after finalize", |fw, s| {
+            let finalize = fw.airdrop_finalize(s.airdrop)?;
+            let add = fw.airdrop_add_recipients(recipients(&fw.victim), s.airdrop, 1)?;
+            Ok((
+                vec![
+                    Step::new("airdrop_finalize", true, finalize),
+                    Step::new("airdrop_add_recipients", false, add),
+                ],
+                vec![],
+            ))
+        }),
+        ("claim after expiry", |fw, s| {
+            let finalize = fw.airdrop_finalize(s.airdrop)?;
+            fw.warp_to(s.expire_at + 1);
+            let claim = fw.airdrop_claim(&s.recipient, s.airdrop)?;
+            Ok((
+                vec![
+                    Step::new("airdrop_finalize", true, finalize),
+                    Step::new("airdrop_claim after expiry", false, claim),
+                ],
+                vec![],
+            ))
+        }),
+        ("close before expiry", |fw, s| {
+            let finalize = fw.airdrop_finalize(s.airdrop)?;
+            let close = fw.airdrop_close(s.airdrop)?;
+            Ok((
+                vec![
+                    Step::new("airdrop_finalize", true, finalize),
+                    Step::new("airdrop_close before expiry", false, close),
+                ],
+                fw.check_airdrop(&s.airdrop),
+            ))
+        }),
+        ("close after expiry with leftovers", |fw, s| {
+            let finalize = fw.airdrop_finalize(s.airdrop)?;
+            let claim = fw.airdrop_claim(&s.recipient, s.airdrop)?;
+            fw.warp_to(s.expire_at + 1);
+            let authority = fw.pool().token_account(&fw.pool_authority.pubkey());
+            let before = fw.token_balance(&authority).unwrap_or(0);
+            let close = fw.airdrop_close(s.airdrop)?;
+            let after = fw.token_balance(&authority).unwrap_or(0);
+
+            let mut violations = fw.check_airdrop(&s.airdrop);
+            match after.checked_sub(before) {
+                None => violations.push(format!(
+                    "closing took {} tokens from the pool authority",
+                    before - after
+                )),
+                Some(returned) if close.is_ok() && returned != VAULT_AMOUNT - CLAIM_AMOUNT => {
+                    violations.push(format!(
+                        "closing returned {} leftover tokens, expected {}",
+                        returned,
+                        VAULT_AMOUNT - CLAIM_AMOUNT
+                    ))
+                }
+                Some(_) => {}
+            }
+            Ok((
+                vec![
+                    Step::new("airdrop_finalize", true, finalize),
+                    Step::new("airdrop_claim", true, claim),
+                    Step::new("airdrop_close after expiry", true, close),
+                ],
+                violations,
+            ))
+        }),
+    ]
+}
+
+/// runs every airdrop lifecycle scenario in a fresh environment, comparing each transition with
+/// what the rewards program is expected to allow. Scenarios run in parallel on `threads` workers
+pub fn run_airdrop_suite(threads: usize) -> Result<SuiteReport, Box<dyn Error>> {
+    let jobs: Vec<(String, Job<ScenarioResult>)> = scenarios()
+        .into_iter()
+        .map(|(scenario, run)| {
+            let job: Job<ScenarioResult> = Box::new(move || {
+                let mut test_env = Framework::new()?;
+                test_env.quiet = true;
+                let setup = setup(&mut test_env)?;
+                let (steps, violations) = run(&mut test_env, &setup)?;
+                Ok(ScenarioResult {
+                    scenario,
+                    steps,
+                    violations,
+                })
+            });
+            (scenario.to_string(), job)
+        })
+        .collect();
+
+    let mut results = vec![];
+    for run in run_parallel(jobs, threads) {
+        out!("{}", run.output);
+        match run.result {
+            Ok(result) => results.push(result),
+            Err(err) => return Err(format!("{}: {}", run.name, err).into()),
+        }
+    }
+
+    Ok(SuiteReport { results })
+}
@@ -0,0 +1,180 @@
+use super::{
+    clone_keypair, lamports::RentLedger, profile::ComputeProfile, report::RunReport, Framework,
+};
+use poc_framework_osec::{
+    solana_sdk::{
+        pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::transfer,
+    },
+    Environment, LocalEnvironment,
+};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+/// configures the vault token mint before the environment is set up
+///
+/// ```ignore
+/// let mut test_env = Framework::builder()
+///     .decimals(0)
+///     .freeze_authority(&freezer)
+///     .build()?;
+/// ```
+pub struct FrameworkBuilder {
+    decimals: u8,
+    mint_authority: Option<Keypair>,
+    freeze_authority: Option<Keypair>,
+    initial_supply: u64,
+    programs: Vec<(Pubkey, PathBuf)>,
+    governance: Option<Pubkey>,
+}
+
+impl Default for FrameworkBuilder {
+    fn default() -> Self {
+        Self {
+            decimals: 9,
+            mint_authority: None,
+            freeze_authority: None,
+            initial_supply: 0,
+            programs: vec![],
+            governance: None,
+        }
+    }
+}
+
+impl FrameworkBuilder {
+    pub fn decimals(&mut self, decimals: u8) -> &mut Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// defaults to the pool authority
+    pub fn mint_authority(&mut self, authority: &Keypair) -> &mut Self {
+        self.mint_authority = Some(clone_keypair(authority));
+        self
+    }
+
+    /// defaults to none, so nothing can be frozen
+    pub fn freeze_authority(&mut self, authority: &Keypair) -> &mut Self {
+        self.freeze_authority = Some(clone_keypair(authority));
+        self
+    }
+
+    /// tokens minted to the mint authority's token account before anything else happens
+    pub fn initial_supply(&mut self, supply: u64) -> &mut Self {
+        self.initial_supply = supply;
+        self
+    }
+
+    /// loads another program from a local `.so`, next to the jet programs
+    pub fn program(&mut self, program_id: Pubkey, path: impl AsRef<Path>) -> &mut Self {
+        self.programs
+            .push((program_id, path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// loads an spl-governance build, enabling the realm and proposal helpers
+    pub fn governance(&mut self, program_id: Pubkey, path: impl AsRef<Path>) -> &mut Self {
+        self.governance = Some(program_id);
+        self.program(program_id, path)
+    }
+
+    pub fn build(&self) -> Result<Framework, Box<dyn Error>> {
+        let mut env_builder = LocalEnvironment::builder();
+
+        let base_path = "./build/jet-governance/target/bpfel-unknown-unknown/release".to_owned();
+        env_builder.add_program(
+            jet_auth::id(),
+            Path::new(&(base_path.clone() + "/jet_auth.so")),
+        );
+        env_builder.add_program(
+            jet_staking::id(),
+            Path::new(&(base_path.clone() + "/jet_staking.so")),
+        );
+        env_builder.add_program(
+            jet_rewards::id(),
+            Path::new(&(base_path + "/jet_rewards.so")),
+        );
+        for (program_id, path) in &self.programs {
+            env_builder.add_program(*program_id, path);
+        }
+
+        let mut env = env_builder.build();
+
+        let attacker = Keypair::new();
+        let victim = Keypair::new();
+        let pool_authority = Keypair::new();
+
+        // fund the accounts
+        env.execute_as_transaction(
+            &[transfer(
+                &env.payer().pubkey(),
+                &attacker.pubkey(),
+                100000000000,
+            )],
+            &[&env.payer()],
+        );
+        env.execute_as_transaction(
+            &[transfer(
+                &env.payer().pubkey(),
+                &victim.pubkey(),
+                100000000000,
+            )],
+            &[&env.payer()],
+        );
+        env.execute_as_transaction(
+            &[transfer(
+                &env.payer().pubkey(),
+                &pool_authority.pubkey(),
+                100000000000,
+            )],
+            &[&env.payer()],
+        );
+
+        // create vault token
+        let vault_token_mint = Keypair::new();
+        let vault_mint_authority = self
+            .mint_authority
+            .as_ref()
+            .map(clone_keypair)
+            .unwrap_or_else(|| clone_keypair(&pool_authority));
+        let vault_freeze_authority = self.freeze_authority.as_ref().map(clone_keypair);
+        env.create_token_mint(
+            &vault_token_mint,
+            vault_mint_authority.pubkey(),
+            vault_freeze_authority
+                .as_ref()
+                .map(|authority| authority.pubkey()),
+            self.decimals,
+        );
+        if self.initial_supply > 0 {
+            let account = env
+                .create_associated_token_account(&vault_mint_authority, vault_token_mint.pubkey());
+            env.mint_tokens(
+                vault_token_mint.pubkey(),
+                &vault_mint_authority,
+                account,
+                self.initial_supply,
+            );
+        }
+
+        Ok(Framework {
+            env,
+            victim,
+            attacker,
+            pool_authority,
+            vault_token_mint,
+            vault_mint_authority,
+            vault_freeze_authority,
+            governance_program: self.governance,
+            seed: "seed".into(),
+            profile: ComputeProfile::default(),
+            rent: RentLedger::default(),
+            report: RunReport::default(),
+            print_traces: false,
+            quiet: false,
+            captured: None,
+            unique_transactions: true,
+        })
+    }
+}
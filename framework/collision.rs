@@ -0,0 +1,240 @@
+use super::{
+    pda::{schemes, PdaScheme, SeedPart},
+    program_name,
+};
+use anchor_lang::solana_program::{
+    hash::hash,
+    pubkey::{Pubkey, MAX_SEED_LEN},
+};
+
+/// user seeds the analyzer starts from when both schemes take one
+const BASE_SEED: &str = "jet";
+
+/// two derivations producing the same address
+pub struct Collision {
+    pub a: PdaScheme,
+    pub b: PdaScheme,
+    /// the user seed given to `a`, `None` if it takes none
+    pub a_seed: Option<String>,
+    pub b_seed: Option<String>,
+    /// an example address both derive to, see [`role_key`] for the pubkey inputs
+    pub address: Pubkey,
+}
+
+impl Collision {
+    pub fn describe(&self) -> String {
+        if self.a.derivation == self.b.derivation {
+            return format!(
+                "{} is bound to nothing but the seed, any two callers using the same seed share an account",
+                self.a.describe()
+            );
+        }
+
+        let input = |seed: &Option<String>| match seed {
+            Some(seed) => format!("seed {:?}", seed),
+            None => "the same pubkeys".to_string(),
+        };
+        format!(
+            "{}::{} with {} == {} with {}",
+            program_name(&self.a.program),
+            self.a.derivation,
+            input(&self.a_seed),
+            self.b.derivation,
+            input(&self.b_seed)
+        )
+    }
+}
+
+/// the fixed bytes before and after the user seed, and whether there is one
+struct Split {
+    pre: Vec<u8>,
+    seed: bool,
+    post: Vec<u8>,
+}
+
+/// the key standing in for every pubkey input with this role, so inputs of the same role are
+/// the same account and inputs of different roles never are
+pub fn role_key(role: &str) -> Pubkey {
+    Pubkey::new_from_array(hash(role.as_bytes()).to_bytes())
+}
+
+fn part_bytes(part: &SeedPart) -> Vec<u8> {
+    match part {
+        SeedPart::Literal(literal) => literal.as_bytes().to_vec(),
+        SeedPart::UserSeed => vec![],
+        SeedPart::Pubkey(role) => role_key(role).to_bytes().to_vec(),
+        SeedPart::Derived(derivation) => {
+            let parent = schemes()
+                .into_iter()
+                .find(|scheme| scheme.derivation == *derivation)
+                .unwrap_or_else(|| panic!("no pda scheme {}", derivation));
+            derive(&parent, &Some(BASE_SEED.to_string()))
+                .to_bytes()
+                .to_vec()
+        }
+        SeedPart::U32(_) => 0u32.to_le_bytes().to_vec(),
+    }
+}
+
+fn split(scheme: &PdaScheme) -> Split {
+    let mut split = Split {
+        pre: vec![],
+        seed: false,
+        post: vec![],
+    };
+    for part in &scheme.seeds {
+        if let SeedPart::UserSeed = part {
+            split.seed = true;
+        } else if split.seed {
+            split.post.extend(part_bytes(part));
+        } else {
+            split.pre.extend(part_bytes(part));
+        }
+    }
+
+    split
+}
+
+/// the seed making `split` hash the same bytes as `target`, if there is a valid one
+fn solve(split: &Split, target: &[u8]) -> Option<String> {
+    if target.len() < split.pre.len() + split.post.len()
+        || !target.starts_with(&split.pre)
+        || !target.ends_with(&split.post)
+    {
+        return None;
+    }
+    let seed = &target[split.pre.len()..target.len() - split.post.len()];
+    if seed.len() > MAX_SEED_LEN {
+        return None;
+    }
+
+    String::from_utf8(seed.to_vec()).ok()
+}
+
+fn concat(split: &Split, seed: &str) -> Vec<u8> {
+    [&split.pre[..], seed.as_bytes(), &split.post[..]].concat()
+}
+
+/// seeds are hashed back to back, so only the concatenated bytes matter and a user seed can
+/// absorb the literals of another scheme. raw pubkey bytes are never absorbed, as they are
+/// hardly ever valid utf-8
+fn seeds_for(a: &PdaScheme, b: &PdaScheme) -> Option<(Option<String>, Option<String>)> {
+    let (sa, sb) = (split(a), split(b));
+    match (sa.seed, sb.seed) {
+        (false, false) => (sa.pre == sb.pre).then(|| (None, None)),
+        (true, false) => solve(&sa, &sb.pre).map(|seed| (Some(seed), None)),
+        (false, true) => solve(&sb, &sa.pre).map(|seed| (None, Some(seed))),
+        (true, true) => solve(&sa, &concat(&sb, BASE_SEED))
+            .map(|seed| (Some(seed), Some(BASE_SEED.to_string())))
+            .or_else(|| {
+                solve(&sb, &concat(&sa, BASE_SEED))
+                    .map(|seed| (Some(BASE_SEED.to_string()), Some(seed)))
+            }),
+    }
+}
+
+fn derive(scheme: &PdaScheme, seed: &Option<String>) -> Pubkey {
+    let seeds: Vec<Vec<u8>> = scheme
+        .seeds
+        .iter()
+        .map(|part| match part {
+            SeedPart::UserSeed => seed.clone().unwrap_or_default().into_bytes(),
+            _ => part_bytes(part),
+        })
+        .collect();
+    let seeds: Vec<&[u8]> = seeds.iter().map(|seed| &seed[..]).collect();
+
+    Pubkey::find_program_address(&seeds, &scheme.program).0
+}
+
+/// every pair of framework derivations that can produce the same address. schemes of different
+/// programs never collide since the program id is part of the hash, so only pairs within one
+/// program are checked. a scheme collides with itself when nothing but a user seed picks the
+/// account, e.g. two creators using the same distribution seed
+pub fn find_collisions() -> Vec<Collision> {
+    let schemes = schemes();
+    let mut collisions = vec![];
+
+    for (i, a) in schemes.iter().enumerate() {
+        if a.takes_user_seed()
+            && !a
+                .seeds
+                .iter()
+                .any(|part| matches!(part, SeedPart::Pubkey(_) | SeedPart::Derived(_)))
+        {
+            let seed = Some(BASE_SEED.to_string());
+            collisions.push(Collision {
+                a: a.clone(),
+                b: a.clone(),
+                a_seed: seed.clone(),
+                b_seed: seed.clone(),
+                address: derive(a, &seed),
+            });
+        }
+
+        for b in schemes[i + 1..].iter().filter(|b| b.program == a.program) {
+            let (a_seed, b_seed) = match seeds_for(a, b) {
+                Some(seeds) => seeds,
+                None => continue,
+            };
+            let address = derive(a, &a_seed);
+            // the analysis is on bytes, make sure the runtime agrees
+            if address == derive(b, &b_seed) {
+                collisions.push(Collision {
+                    a: a.clone(),
+                    b: b.clone(),
+                    a_seed,
+                    b_seed,
+                    address,
+                });
+            }
+        }
+    }
+
+    collisions
+}
+
+pub fn print_collisions() {
+    let collisions = find_collisions();
+    outln!("{} pda collisions found", collisions.len());
+    for collision in collisions {
+        outln!("  {}", collision.describe());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn award_seeds_dont_collide_with_reward_vaults() {
+        // award_pubkey(K, "vault") hashes the same bytes as reward_vault_pubkey(K), but K is a
+        // stake account for one and an award for the other
+        let reported = find_collisions().into_iter().any(|collision| {
+            [&collision.a, &collision.b]
+                .iter()
+                .any(|scheme| scheme.derivation == "award_pubkey")
+                && [&collision.a, &collision.b]
+                    .iter()
+                    .any(|scheme| scheme.derivation.starts_with("reward_vault_pubkey"))
+        });
+        assert!(!reported);
+    }
+
+    #[test]
+    fn pool_seeds_can_absorb_the_vault_literal() {
+        // stake_pool_pubkey("jetvault") hashes the same bytes as stake_pool_vault_pubkey("jet")
+        let collision = find_collisions()
+            .into_iter()
+            .find(|collision| {
+                collision.a.derivation == "stake_pool_pubkey"
+                    && collision.b.derivation == "stake_pool_vault_pubkey"
+            })
+            .unwrap();
+        assert_eq!(collision.a_seed.as_deref(), Some("jetvault"));
+        assert_eq!(collision.b_seed.as_deref(), Some("jet"));
+
+        let (vault, _) = Pubkey::find_program_address(&[b"jet", b"vault"], &jet_staking::id());
+        assert_eq!(collision.address, vault);
+    }
+}
@@ -0,0 +1,202 @@
+//! Decodes the data of jet instructions back into the instruction name and its arguments, the
+//! inverse of the [`super::ix`] builders. Used to make transaction reports readable.
+
+use anchor_lang::{
+    solana_program::{hash, pubkey::Pubkey},
+    AnchorDeserialize,
+};
+use jet_staking::{Amount, AmountKind};
+use serde_json::{json, Value};
+
+const AUTH_INSTRUCTIONS: &[&str] = &["create_user_auth", "authenticate"];
+
+const STAKING_INSTRUCTIONS: &[&str] = &[
+    "init_pool",
+    "init_stake_account",
+    "add_stake",
+    "unbond_stake",
+    "cancel_unbond",
+    "withdraw_unbonded",
+    "withdraw_bonded",
+    "mint_votes",
+    "burn_votes",
+    "close_stake_account",
+];
+
+const REWARDS_INSTRUCTIONS: &[&str] = &[
+    "award_create",
+    "award_release",
+    "award_revoke",
+    "award_close",
+    "distribution_create",
+    "distribution_release",
+    "distribution_close",
+    "airdrop_create",
+    "airdrop_add_recipients",
+    "airdrop_finalize",
+    "airdrop_claim",
+    "airdrop_close",
+];
+
+/// the 8 bytes anchor prefixes the data of instruction `name` with
+pub fn sighash(name: &str) -> [u8; 8] {
+    let mut sighash = [0; 8];
+    sighash.copy_from_slice(&hash::hash(format!("global:{}", name).as_bytes()).to_bytes()[..8]);
+
+    sighash
+}
+
+#[derive(Clone, Debug)]
+pub struct DecodedInstruction {
+    pub name: &'static str,
+    /// `None` if the arguments don't deserialize, e.g. the data was tampered with
+    pub args: Option<Value>,
+}
+
+/// names the jet instruction `data` is for and decodes its arguments, `None` for other programs
+/// or an unknown sighash
+pub fn decode(program: &Pubkey, data: &[u8]) -> Option<DecodedInstruction> {
+    let names = if *program == jet_auth::id() {
+        AUTH_INSTRUCTIONS
+    } else if *program == jet_staking::id() {
+        STAKING_INSTRUCTIONS
+    } else if *program == jet_rewards::id() {
+        REWARDS_INSTRUCTIONS
+    } else {
+        return None;
+    };
+    if data.len() < 8 {
+        return None;
+    }
+
+    let (prefix, args) = data.split_at(8);
+    let name = *names.iter().find(|name| sighash(name) == prefix)?;
+    Some(DecodedInstruction {
+        name,
+        args: decode_args(name, args),
+    })
+}
+
+fn amount(amount: &Amount) -> Value {
+    let kind = match amount.kind {
+        AmountKind::Tokens => "tokens",
+        AmountKind::Shares => "shares",
+    };
+
+    json!({ "kind": kind, "value": amount.value })
+}
+
+fn decode_args(name: &str, data: &[u8]) -> Option<Value> {
+    use jet_rewards::instruction as rewards;
+    use jet_staking::instruction as staking;
+
+    Some(match name {
+        "init_pool" => {
+            let ix = staking::InitPool::try_from_slice(data).ok()?;
+            json!({ "seed": ix.seed, "config": { "unbond_period": ix.config.unbond_period } })
+        }
+        "add_stake" => {
+            let ix = staking::AddStake::try_from_slice(data).ok()?;
+            json!({ "amount": amount(&ix.amount) })
+        }
+        "unbond_stake" => {
+            let ix = staking::UnbondStake::try_from_slice(data).ok()?;
+            json!({ "seed": ix.seed, "amount": amount(&ix.amount) })
+        }
+        "withdraw_bonded" => {
+            let ix = staking::WithdrawBonded::try_from_slice(data).ok()?;
+            json!({ "amount": ix.amount })
+        }
+        "mint_votes" => {
+            let ix = staking::MintVotes::try_from_slice(data).ok()?;
+            json!({ "amount": amount(&ix.amount) })
+        }
+        "burn_votes" => {
+            let ix = staking::BurnVotes::try_from_slice(data).ok()?;
+            json!({ "amount": ix.amount })
+        }
+        "award_create" => {
+            let params = rewards::AwardCreate::try_from_slice(data).ok()?.params;
+            json!({
+                "seed": params.seed,
+                "authority": params.authority.to_string(),
+                "stake_account": params.stake_account.to_string(),
+                "amount": params.amount,
+                "begin_at": params.begin_at,
+                "end_at": params.end_at,
+            })
+        }
+        "distribution_create" => {
+            let params = rewards::DistributionCreate::try_from_slice(data)
+                .ok()?
+                .params;
+            json!({
+                "seed": params.seed,
+                "authority": params.authority.to_string(),
+                "amount": params.amount,
+                "begin_at": params.begin_at,
+                "end_at": params.end_at,
+                "target_account": params.target_account.to_string(),
+            })
+        }
+        "airdrop_create" => {
+            let params = rewards::AirdropCreate::try_from_slice(data).ok()?.params;
+            json!({
+                "expire_at": params.expire_at,
+                "stake_pool": params.stake_pool.to_string(),
+                "short_desc": params.short_desc,
+                "flags": params.flags,
+            })
+        }
+        "airdrop_add_recipients" => {
+            let params = rewards::AirdropAddRecipients::try_from_slice(data)
+                .ok()?
+                .params;
+            let recipients: Vec<Value> = params
+                .recipients
+                .iter()
+                .map(|param| {
+                    json!({ "recipient": param.recipient.to_string(), "amount": param.amount })
+                })
+                .collect();
+            json!({ "start_index": params.start_index, "recipients": recipients })
+        }
+        // everything else takes no arguments
+        _ if data.is_empty() => json!({}),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::ix;
+
+    #[test]
+    fn decodes_what_ix_builds() {
+        let pool = ix::Pool {
+            seed: "pool".to_string(),
+            authority: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+        };
+        let owner = Pubkey::new_unique();
+
+        let add_stake = ix::add_stake(&pool, &owner, 100);
+        let decoded = decode(&add_stake.program_id, &add_stake.data).unwrap();
+        assert_eq!(decoded.name, "add_stake");
+        assert_eq!(
+            decoded.args,
+            Some(json!({ "amount": { "kind": "tokens", "value": 100 } }))
+        );
+
+        let release = ix::award_release(&pool, &owner, "award");
+        let decoded = decode(&release.program_id, &release.data).unwrap();
+        assert_eq!(decoded.name, "award_release");
+        assert_eq!(decoded.args, Some(json!({})));
+
+        let mut tampered = add_stake.data.clone();
+        tampered.push(0);
+        assert_eq!(decode(&add_stake.program_id, &tampered).unwrap().args, None);
+        assert!(decode(&spl_token::id(), &add_stake.data).is_none());
+    }
+}
@@ -0,0 +1,158 @@
+use anchor_lang::{AnchorDeserialize, Discriminator, Event};
+use jet_rewards::events as rewards_events;
+use jet_staking::events as staking_events;
+use poc_framework_osec::solana_sdk::pubkey::Pubkey;
+
+/// decodes the payload of a `Program data:` line if it carries an event of type `T`
+pub fn decode_event<T: Event>(data: &[u8]) -> Option<T> {
+    if data.len() < 8 || data[..8] != T::discriminator() {
+        return None;
+    }
+
+    T::deserialize(&mut &data[8..]).ok()
+}
+
+macro_rules! program_events {
+    ($name:ident, $module:ident, [$($event:ident),* $(,)?]) => {
+        pub enum $name {
+            $($event($module::$event),)*
+        }
+
+        impl $name {
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                $(
+                    if let Some(event) = decode_event::<$module::$event>(data) {
+                        return Some($name::$event(event));
+                    }
+                )*
+
+                None
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $($name::$event(_) => stringify!($event),)*
+                }
+            }
+        }
+    };
+}
+
+program_events!(
+    StakingEvent,
+    staking_events,
+    [
+        StakePoolCreated,
+        StakeAccountCreated,
+        StakeAdded,
+        StakeUnbonded,
+        UnbondCancelled,
+        UnbondedWithdrawn,
+        BondedWithdrawn,
+        VotesMinted,
+        VotesBurned,
+        StakeAccountClosed,
+    ]
+);
+
+program_events!(
+    RewardsEvent,
+    rewards_events,
+    [
+        AirdropCreated,
+        AirdropRecipientsAdded,
+        AirdropFinalized,
+        AirdropClaimed,
+        AirdropClosed,
+        AwardCreated,
+        AwardReleased,
+        AwardRevoked,
+        AwardClosed,
+        DistributionCreated,
+        DistributionReleased,
+        DistributionClosed,
+    ]
+);
+
+pub enum JetEvent {
+    Staking(StakingEvent),
+    Rewards(RewardsEvent),
+    /// data logged by a program we don't have event types for
+    Unknown {
+        program: Pubkey,
+        data: Vec<u8>,
+    },
+}
+
+impl JetEvent {
+    pub fn decode(program: Pubkey, data: &[u8]) -> Self {
+        let event = if program == jet_staking::id() {
+            StakingEvent::decode(data).map(JetEvent::Staking)
+        } else if program == jet_rewards::id() {
+            RewardsEvent::decode(data).map(JetEvent::Rewards)
+        } else {
+            None
+        };
+
+        event.unwrap_or_else(|| JetEvent::Unknown {
+            program,
+            data: data.to_vec(),
+        })
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            JetEvent::Staking(event) => format!("jet_staking::{}", event.name()),
+            JetEvent::Rewards(event) => format!("jet_rewards::{}", event.name()),
+            JetEvent::Unknown { program, data } => {
+                format!("{}::<{} bytes>", super::program_name(program), data.len())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the logged bytes of an event of type `T` with every field zeroed
+    fn zeroed<T: Event>() -> Vec<u8> {
+        let zeros = [0u8; 1024];
+        T::deserialize(&mut &zeros[..]).unwrap().data()
+    }
+
+    #[test]
+    fn events_are_matched_by_discriminator() {
+        let data = zeroed::<staking_events::StakeAdded>();
+        assert!(decode_event::<staking_events::StakeAdded>(&data).is_some());
+        assert_eq!(StakingEvent::decode(&data).unwrap().name(), "StakeAdded");
+        assert_eq!(
+            JetEvent::decode(jet_staking::id(), &data).name(),
+            "jet_staking::StakeAdded"
+        );
+    }
+
+    #[test]
+    fn foreign_data_is_rejected() {
+        let data = zeroed::<staking_events::VotesMinted>();
+        assert!(decode_event::<staking_events::StakeAdded>(&data).is_none());
+        assert!(RewardsEvent::decode(&data).is_none());
+        assert!(matches!(
+            JetEvent::decode(jet_rewards::id(), &data),
+            JetEvent::Unknown { .. }
+        ));
+        assert!(matches!(
+            JetEvent::decode(spl_token::id(), &data),
+            JetEvent::Unknown { .. }
+        ));
+    }
+
+    #[test]
+    fn short_data_is_rejected() {
+        let data = zeroed::<staking_events::StakeAdded>();
+        assert!(decode_event::<staking_events::StakeAdded>(&data[..7]).is_none());
+        assert!(decode_event::<staking_events::StakeAdded>(&data[..8]).is_none());
+        assert!(decode_event::<staking_events::StakeAdded>(&data[..data.len() - 1]).is_none());
+        assert!(decode_event::<staking_events::StakeAdded>(&[]).is_none());
+    }
+}
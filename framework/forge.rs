@@ -0,0 +1,143 @@
+//! Writing arbitrary state straight into the environment.
+//!
+//! Lets a scenario start from an edge state, or hand programs hostile pre-existing data, without
+//! replaying the instructions that would lead there:
+//!
+//! ```ignore
+//! let mut stake_pool: StakePool = test_env.read_account(&test_env.stake_pool_pubkey())?;
+//! stake_pool.shares_unbonded = u64::MAX;
+//! test_env.forge_stake_pool(&stake_pool)?;
+//! test_env.forge_token_account(&test_env.stake_pool_vault_pubkey(), &mint, &owner, 0);
+//! ```
+
+use super::Framework;
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use jet_staking::state::{StakeAccount, StakePool};
+use poc_framework_osec::{
+    solana_sdk::{account::Account, program_pack::Pack, pubkey::Pubkey},
+    Environment,
+};
+use spl_token::{
+    solana_program::program_option::COption,
+    state::{Account as TokenAccount, AccountState, Mint},
+};
+use std::error::Error;
+
+impl Framework {
+    /// replaces whatever is at `pubkey` with the given account
+    pub fn set_account(&mut self, pubkey: &Pubkey, owner: &Pubkey, lamports: u64, data: Vec<u8>) {
+        self.store_account(
+            pubkey,
+            Account {
+                lamports,
+                data,
+                owner: *owner,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+    }
+
+    /// like `set_account`, funded with exactly the rent exemption for the data
+    pub fn set_rent_exempt_account(&mut self, pubkey: &Pubkey, owner: &Pubkey, data: Vec<u8>) {
+        let lamports = self.env.get_rent_excemption(data.len());
+        self.set_account(pubkey, owner, lamports, data);
+    }
+
+    /// writes an anchor account, discriminator included. The account keeps the size of whatever is
+    /// at `pubkey`, zero padded like the space anchor allocates, so programs reading it at its
+    /// allocated size still can. A new account is as long as the serialized data
+    pub fn forge_anchor_account<T: AccountSerialize>(
+        &mut self,
+        pubkey: &Pubkey,
+        owner: &Pubkey,
+        account: &T,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut data = vec![];
+        account.try_serialize(&mut data)?;
+        if let Some(existing) = self.env.get_account(*pubkey) {
+            if data.len() > existing.data.len() {
+                return Err(format!(
+                    "{} bytes don't fit the {} bytes of account {}",
+                    data.len(),
+                    existing.data.len(),
+                    pubkey
+                )
+                .into());
+            }
+            data.resize(existing.data.len(), 0);
+        }
+        self.set_rent_exempt_account(pubkey, owner, data);
+
+        Ok(())
+    }
+
+    /// reads back an anchor account, to tweak a few fields before forging it again
+    pub fn read_account<T: AccountDeserialize>(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Result<T, Box<dyn Error>> {
+        let account = self
+            .env
+            .get_account(*pubkey)
+            .ok_or_else(|| format!("account {} doesn't exist", pubkey))?;
+
+        Ok(T::try_deserialize(&mut account.data.as_slice())?)
+    }
+
+    /// overwrites the framework's stake pool
+    pub fn forge_stake_pool(&mut self, stake_pool: &StakePool) -> Result<(), Box<dyn Error>> {
+        let pubkey = self.stake_pool_pubkey();
+        self.forge_anchor_account(&pubkey, &jet_staking::id(), stake_pool)
+    }
+
+    /// overwrites the stake account of `owner` in the framework's stake pool
+    pub fn forge_stake_account(
+        &mut self,
+        owner: &Pubkey,
+        stake_account: &StakeAccount,
+    ) -> Result<(), Box<dyn Error>> {
+        let pubkey = self.pool().stake_account(owner);
+        self.forge_anchor_account(&pubkey, &jet_staking::id(), stake_account)
+    }
+
+    /// an initialized token account with any balance, no mint_to needed
+    pub fn forge_token_account(
+        &mut self,
+        pubkey: &Pubkey,
+        mint: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+    ) {
+        let mut data = vec![0; TokenAccount::LEN];
+        TokenAccount::pack(
+            TokenAccount {
+                mint: *mint,
+                owner: *owner,
+                amount,
+                state: AccountState::Initialized,
+                ..TokenAccount::default()
+            },
+            &mut data,
+        )
+        .unwrap();
+        self.set_rent_exempt_account(pubkey, &spl_token::id(), data);
+    }
+
+    /// a mint with any supply, `authority` being the mint authority
+    pub fn forge_mint(&mut self, pubkey: &Pubkey, authority: &Pubkey, supply: u64, decimals: u8) {
+        let mut data = vec![0; Mint::LEN];
+        Mint::pack(
+            Mint {
+                mint_authority: COption::Some(*authority),
+                supply,
+                decimals,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            },
+            &mut data,
+        )
+        .unwrap();
+        self.set_rent_exempt_account(pubkey, &spl_token::id(), data);
+    }
+}
@@ -0,0 +1,44 @@
+use super::Framework;
+use poc_framework_osec::{
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        pubkey::Pubkey,
+    },
+    Environment,
+};
+
+/// a copy of some accounts, to roll the environment back after trying something destructive
+pub struct Fork {
+    accounts: Vec<(Pubkey, Option<Account>)>,
+}
+
+impl Fork {
+    pub fn pubkeys(&self) -> Vec<Pubkey> {
+        self.accounts.iter().map(|(pubkey, _)| *pubkey).collect()
+    }
+}
+
+impl Framework {
+    pub fn fork(&self, pubkeys: &[Pubkey]) -> Fork {
+        Fork {
+            accounts: pubkeys
+                .iter()
+                .map(|pubkey| (*pubkey, self.env.get_account(*pubkey)))
+                .collect(),
+        }
+    }
+
+    /// puts every forked account back the way it was, removing the ones that didn't exist yet
+    pub fn restore(&mut self, fork: &Fork) {
+        for (pubkey, account) in &fork.accounts {
+            self.store_account(pubkey, account.clone().unwrap_or_default());
+        }
+    }
+
+    /// writes an account straight into the bank, a zero lamport account removes it
+    pub(super) fn store_account(&mut self, pubkey: &Pubkey, account: Account) {
+        self.env
+            .bank()
+            .store_account(pubkey, &AccountSharedData::from(account));
+    }
+}
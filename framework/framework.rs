@@ -1,230 +1,288 @@
-use anchor_client::{
-    anchor_lang::System,
-    solana_sdk::{
-        instruction::Instruction, program_pack::Pack, system_instruction::transfer,
-        transaction::Transaction,
-    },
-    Program,
-};
-use anchor_lang::{solana_program, Id};
+use anchor_lang::{Id, System};
 use anchor_spl::associated_token::get_associated_token_address;
-use jet_auth::accounts::{Authenticate, CreateUserAuthentication};
-use jet_staking::{
-    accounts::{AddStake, InitPool, InitStakeAccount},
-    Amount,
-};
+use jet_staking::Amount;
 use poc_framework_osec::{
-    solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer},
+    solana_sdk::{
+        hash::Hash, instruction::Instruction, program_pack::Pack, pubkey::Pubkey,
+        signature::Keypair, signer::Signer, transaction::Transaction,
+    },
     solana_transaction_status::EncodedConfirmedTransaction,
     Environment, LocalEnvironment,
 };
-use std::{error::Error, path::Path, rc::Rc};
+use std::error::Error;
+
+/// `print!` through `runner::write_output`, so a parallel run can capture it per thread
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::framework::runner::write_output(format_args!($($arg)*))
+    };
+}
+
+macro_rules! outln {
+    () => {
+        out!("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::framework::runner::write_output(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub mod airdrop_loader;
+pub mod airdrop_suite;
+pub mod builder;
+pub mod collision;
+pub mod decode;
+pub mod events;
+pub mod forge;
+pub mod fork;
+#[cfg(feature = "governance")]
+pub mod governance;
+pub mod helpers;
+pub mod ix;
+pub mod lamports;
+pub mod limits;
+pub mod outcome;
+pub mod pda;
+pub mod profile;
+pub mod replay;
+pub mod report;
+pub mod rewards;
+pub mod runner;
+pub mod seed_fuzz;
+pub mod signers;
+pub mod squatting;
+pub mod staking;
+pub mod substitution;
+pub mod time;
+pub mod trace;
+pub mod tx_builder;
+pub mod vesting;
+use builder::FrameworkBuilder;
+use lamports::RentLedger;
+use outcome::TxOutcome;
+use profile::ComputeProfile;
+use report::RunReport;
+use trace::Trace;
+use tx_builder::Captured;
 
 pub struct Framework {
     pub env: LocalEnvironment,
     pub victim: Keypair,
     pub attacker: Keypair,
     pub pool_authority: Keypair,
-    auth_program_client: Program,
-    stake_program_client: Program,
-    rewards_program_client: Program,
     pub vault_token_mint: Keypair,
+    pub vault_mint_authority: Keypair,
+    pub vault_freeze_authority: Option<Keypair>,
+    /// set when the framework was built with an spl-governance program
+    pub governance_program: Option<Pubkey>,
     pub seed: String,
-    pub nop_program_pubkey: Pubkey,
-    tx_nonce: u64,
+    pub profile: ComputeProfile,
+    pub rent: RentLedger,
+    pub report: RunReport,
+    /// also print the call tree of successful transactions, failed ones are always printed
+    pub print_traces: bool,
+    /// don't print anything for failed transactions either, for tools expecting lots of failures
+    pub quiet: bool,
+    /// sign every transaction under a fresh blockhash, so sending the same instructions twice
+    /// isn't rejected as a duplicate. Turn off to resend an identical transaction on purpose
+    pub unique_transactions: bool,
+    captured: Option<Vec<Captured>>,
 }
 
+// scenarios on `runner::run_parallel` build their frameworks on worker threads
+const _: fn() = || {
+    fn assert_send<T: Send>() {}
+    assert_send::<Framework>();
+};
+
 impl Framework {
     pub fn process_tx_result(result: EncodedConfirmedTransaction) {
-        let meta = result.transaction.meta.unwrap();
+        let meta = result.transaction.meta.as_ref().unwrap();
 
         if meta.status.is_err() {
-            for line in meta.log_messages.unwrap() {
-                println!("{}", line);
-            }
+            out!("{}", Trace::from_result(&result));
         }
     }
-    pub fn new() -> Result<Self, Box<dyn Error>> {
-        let mut env_builder = LocalEnvironment::builder();
 
-        let base_path = "./build/jet-governance/target/bpfel-unknown-unknown/release".to_owned();
-        env_builder.add_program(
-            jet_auth::id(),
-            Path::new(&(base_path.clone() + "/jet_auth.so")),
-        );
-        env_builder.add_program(
-            jet_staking::id(),
-            Path::new(&(base_path.clone() + "/jet_staking.so")),
-        );
-        env_builder.add_program(
-            jet_rewards::id(),
-            Path::new(&(base_path + "/jet_rewards.so")),
+    /// signs and executes the instructions built by a helper, or only records them while capturing
+    fn execute(
+        &mut self,
+        helper: &str,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> TxOutcome {
+        if let Some(captured) = self.captured.as_mut() {
+            captured.push(Captured {
+                helper: helper.to_string(),
+                instructions: instructions.to_vec(),
+                payer: clone_keypair(payer),
+                signers: signers.iter().map(|signer| clone_keypair(signer)).collect(),
+            });
+            return TxOutcome::captured(helper);
+        }
+
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &signers.to_vec(),
+            self.next_blockhash(),
         );
+        self.execute_transaction(helper, transaction)
+    }
 
-        let mut env = env_builder.build();
+    /// executes an already signed transaction, recording its compute usage under the helper's name
+    fn execute_transaction(&mut self, helper: &str, transaction: Transaction) -> TxOutcome {
+        let result = self.env.execute_transaction(transaction);
+        self.profile.record(helper, &result);
+        self.rent.record(helper, &result);
+        let outcome = TxOutcome::new(helper, result);
+        self.report.record(&outcome);
+        if self.print_traces && outcome.is_ok() {
+            outln!("{}:", helper);
+            out!("{}", outcome.trace);
+            for event in &outcome.events {
+                outln!("  event: {}", event.name());
+            }
+        }
+        if !self.quiet {
+            Framework::process_tx_result(outcome.result.clone().unwrap());
+        }
 
-        let attacker = Keypair::new();
-        let victim = Keypair::new();
-        let pool_authority = Keypair::new();
+        outcome
+    }
 
-        let rpc = "https://fake.local".to_owned();
-        let wss = rpc.replace("https", "wss");
-        let connection = anchor_client::Client::new(
-            anchor_client::Cluster::Custom(rpc, wss),
-            Rc::new(clone_keypair(&attacker)),
-        );
-        let auth_program_client = connection.program(jet_auth::id());
-        let stake_program_client = connection.program(jet_staking::id());
-        let rewards_program_client = connection.program(jet_rewards::id());
-
-        // deploy nop program
-        let nop_program_pubkey = env.deploy_program("../framework/nop.so");
-
-        // fund the accounts
-        env.execute_as_transaction(
-            &[transfer(
-                &env.payer().pubkey(),
-                &attacker.pubkey(),
-                100000000000,
-            )],
-            &[&env.payer()],
-        );
-        env.execute_as_transaction(
-            &[transfer(
-                &env.payer().pubkey(),
-                &victim.pubkey(),
-                100000000000,
-            )],
-            &[&env.payer()],
-        );
-        env.execute_as_transaction(
-            &[transfer(
-                &env.payer().pubkey(),
-                &pool_authority.pubkey(),
-                100000000000,
-            )],
-            &[&env.payer()],
+    /// like `execute`, but without printing, profiling or capturing, for tools that expect most
+    /// attempts to fail. Keypairs the instructions don't ask a signature from are left out.
+    fn execute_quietly(
+        &mut self,
+        helper: &str,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> TxOutcome {
+        let required: Vec<Pubkey> = instructions
+            .iter()
+            .flat_map(|ix| ix.accounts.iter())
+            .filter(|meta| meta.is_signer)
+            .map(|meta| meta.pubkey)
+            .collect();
+        let mut signers_used = vec![payer];
+        for signer in signers {
+            if required.contains(&signer.pubkey())
+                && !signers_used.iter().any(|s| s.pubkey() == signer.pubkey())
+            {
+                signers_used.push(signer);
+            }
+        }
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &signers_used,
+            self.next_blockhash(),
         );
 
-        // create vault token
-        let vault_token_mint = Keypair::new();
-        env.create_token_mint(&vault_token_mint, pool_authority.pubkey(), None, 9);
-
-        Ok(Self {
-            env,
-            victim,
-            attacker,
-            pool_authority,
-            auth_program_client,
-            stake_program_client,
-            rewards_program_client,
-            vault_token_mint,
-            seed: "seed".into(),
-            nop_program_pubkey,
-            tx_nonce: 0,
-        })
-    }
-
-    pub fn create_user_auth(&mut self, user: &Keypair) -> Result<(), Box<dyn Error>> {
-        let (auth, _bump) =
-            Pubkey::find_program_address(&[user.pubkey().as_ref()], &self.auth_program_client.id());
-
-        let create_user_auth = CreateUserAuthentication {
-            user: user.pubkey(),
-            payer: user.pubkey(),
-            auth,
-            system_program: System::id(),
-        };
-
-        let create_user_auth_transaction = Transaction::new_signed_with_payer(
-            &self
-                .auth_program_client
-                .request()
-                .accounts(create_user_auth)
-                .args(jet_auth::instruction::CreateUserAuth {})
-                .instructions()?,
-            Some(&user.pubkey()),
-            &vec![user],
-            self.env.get_recent_blockhash(),
-        );
-        let create_user_auth_transaction_out =
-            self.env.execute_transaction(create_user_auth_transaction);
+        self.execute_transaction_quietly(helper, transaction)
+    }
 
-        Framework::process_tx_result(create_user_auth_transaction_out);
+    /// like `execute_transaction`, but without printing, profiling or reporting, for probes that
+    /// aren't part of the scenario itself
+    fn execute_transaction_quietly(&mut self, helper: &str, transaction: Transaction) -> TxOutcome {
+        TxOutcome::new(helper, self.env.execute_transaction(transaction))
+    }
 
-        Ok(())
+    /// runs `operation` without executing anything, returning the instructions and signers of every
+    /// transaction it would have sent so they can be combined with a [`tx_builder::TxBuilder`]
+    pub fn capture<F>(&mut self, operation: F) -> Result<Vec<Captured>, Box<dyn Error>>
+    where
+        F: FnOnce(&mut Framework) -> Result<TxOutcome, Box<dyn Error>>,
+    {
+        self.captured = Some(vec![]);
+        let result = operation(self);
+        let captured = self.captured.take().unwrap_or_default();
+        result?;
+
+        Ok(captured)
     }
 
-    pub fn authenticate_user(&mut self, user: &Keypair) -> Result<(), Box<dyn Error>> {
-        let (auth, _bump) =
-            Pubkey::find_program_address(&[user.pubkey().as_ref()], &self.auth_program_client.id());
-
-        let auth_accounts = Authenticate {
-            auth,
-            // this isn't checked so it doesn't matter
-            authority: self.attacker.pubkey(),
-        };
-        let auth_user_transaction = Transaction::new_signed_with_payer(
-            &self
-                .auth_program_client
-                .request()
-                .accounts(auth_accounts)
-                .args(jet_auth::instruction::Authenticate {})
-                .instructions()?,
-            Some(&user.pubkey()),
-            &vec![user],
-            self.env.get_recent_blockhash(),
-        );
-        let auth_user_transaction_out = self.env.execute_transaction(auth_user_transaction);
-        Framework::process_tx_result(auth_user_transaction_out);
+    pub fn new() -> Result<Self, Box<dyn Error>> {
+        Self::builder().build()
+    }
 
-        Ok(())
+    pub fn builder() -> FrameworkBuilder {
+        FrameworkBuilder::default()
     }
 
-    pub fn init_stake_pool(&mut self) -> Result<(), Box<dyn Error>> {
-        let stake_pool = self.stake_pool_pubkey();
-        let stake_vote_mint = self.stake_vote_mint_pubkey();
-        let (stake_collateral_mint, _bump) = Pubkey::find_program_address(
-            &[self.seed.as_bytes(), b"collateral-mint".as_ref()],
-            &self.stake_program_client.id(),
-        );
-        let stake_pool_vault = self.stake_pool_vault_pubkey();
+    pub fn create_user_auth(&mut self, user: &Keypair) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::create_user_auth(&user.pubkey())];
+        Ok(self.execute("create_user_auth", &instructions, user, &[user]))
+    }
 
-        let init_pool_accounts = InitPool {
-            payer: self.pool_authority.pubkey(),
+    pub fn authenticate_user(&mut self, user: &Keypair) -> Result<TxOutcome, Box<dyn Error>> {
+        // this isn't checked so it doesn't matter
+        let instructions = vec![ix::authenticate(&user.pubkey(), &self.attacker.pubkey())];
+        Ok(self.execute("authenticate_user", &instructions, user, &[user]))
+    }
+
+    pub fn init_stake_pool(&mut self) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::init_pool(&self.pool(), 0)];
+        let pool_authority = clone_keypair(&self.pool_authority);
+        Ok(self.execute(
+            "init_stake_pool",
+            &instructions,
+            &pool_authority,
+            &[&pool_authority],
+        ))
+    }
+
+    /// the stake pool addresses, for use with the [`ix`] builders
+    pub fn pool(&self) -> ix::Pool {
+        ix::Pool {
+            seed: self.seed.clone(),
             authority: self.pool_authority.pubkey(),
             token_mint: self.vault_token_mint.pubkey(),
-            stake_pool,
-            stake_vote_mint,
-            stake_collateral_mint,
-            stake_pool_vault,
-            token_program: spl_token::id(),
-            system_program: System::id(),
-            rent: solana_program::sysvar::rent::id(),
-        };
-        let init_pool_transaction = Transaction::new_signed_with_payer(
-            &self
-                .stake_program_client
-                .request()
-                .accounts(init_pool_accounts)
-                .args(jet_staking::instruction::InitPool {
-                    seed: self.seed.clone(),
-                    config: jet_staking::instructions::PoolConfig { unbond_period: 0 },
-                })
-                .instructions()?,
-            Some(&self.pool_authority.pubkey()),
-            &vec![&self.pool_authority],
-            self.env.get_recent_blockhash(),
-        );
-        let init_pool_transaction_out = self.env.execute_transaction(init_pool_transaction);
-        Framework::process_tx_result(init_pool_transaction_out);
+        }
+    }
 
-        Ok(())
+    /// every address the framework can name, used to label accounts in reports
+    pub fn known_accounts(&self) -> Vec<(String, Pubkey)> {
+        let pool = self.pool();
+        let mut accounts = vec![
+            ("stake pool".to_string(), pool.stake_pool()),
+            ("stake pool vault".to_string(), pool.vault()),
+            ("vote mint".to_string(), pool.vote_mint()),
+            ("collateral mint".to_string(), pool.collateral_mint()),
+            ("vault token mint".to_string(), pool.token_mint),
+        ];
+        for (name, user) in [
+            ("attacker", self.attacker.pubkey()),
+            ("victim", self.victim.pubkey()),
+            ("pool authority", self.pool_authority.pubkey()),
+        ] {
+            accounts.push((name.to_string(), user));
+            accounts.push((format!("{} auth", name), ix::user_auth(&user)));
+            accounts.push((format!("{} stake account", name), pool.stake_account(&user)));
+            accounts.push((format!("{} token account", name), pool.token_account(&user)));
+            accounts.push((
+                format!("{} vote token account", name),
+                get_associated_token_address(&user, &pool.vote_mint()),
+            ));
+        }
+
+        accounts
+    }
+
+    pub fn label(&self, pubkey: &Pubkey) -> String {
+        self.known_accounts()
+            .into_iter()
+            .find(|(_, known)| known == pubkey)
+            .map(|(name, _)| name)
+            .unwrap_or_else(|| program_name(pubkey))
     }
 
     pub fn stake_pool_pubkey(&self) -> Pubkey {
         let (stake_pool, _bump) =
-            Pubkey::find_program_address(&[self.seed.as_bytes()], &self.stake_program_client.id());
+            Pubkey::find_program_address(&[self.seed.as_bytes()], &jet_staking::id());
 
         stake_pool
     }
@@ -232,7 +290,7 @@ impl Framework {
     pub fn stake_pool_vault_pubkey(&self) -> Pubkey {
         let (stake_pool_vault, _bump) = Pubkey::find_program_address(
             &[self.seed.as_bytes(), b"vault".as_ref()],
-            &self.stake_program_client.id(),
+            &jet_staking::id(),
         );
 
         stake_pool_vault
@@ -241,7 +299,7 @@ impl Framework {
     pub fn stake_vote_mint_pubkey(&self) -> Pubkey {
         let (stake_vote_mint, _bump) = Pubkey::find_program_address(
             &[self.seed.as_bytes(), b"vote-mint".as_ref()],
-            &self.stake_program_client.id(),
+            &jet_staking::id(),
         );
 
         stake_vote_mint
@@ -251,79 +309,20 @@ impl Framework {
         let stake_pool = self.stake_pool_pubkey();
         let (stake_account, _bump) = Pubkey::find_program_address(
             &[stake_pool.as_ref(), user.pubkey().as_ref()],
-            &self.stake_program_client.id(),
+            &jet_staking::id(),
         );
 
         stake_account
     }
 
-    pub fn init_stake_account(&mut self, user: &Keypair) -> Result<(), Box<dyn Error>> {
-        let (auth, _bump) =
-            Pubkey::find_program_address(&[user.pubkey().as_ref()], &self.auth_program_client.id());
-
-        let stake_pool = self.stake_pool_pubkey();
-
-        let stake_account = self.stake_account_pubkey(user);
-        let init_stake_account_accounts = InitStakeAccount {
-            owner: user.pubkey(),
-            auth,
-            stake_pool,
-            stake_account,
-            payer: user.pubkey(),
-            system_program: System::id(),
-        };
-        let init_stake_account = Transaction::new_signed_with_payer(
-            &self
-                .stake_program_client
-                .request()
-                .accounts(init_stake_account_accounts)
-                .args(jet_staking::instruction::InitStakeAccount {})
-                .instructions()?,
-            Some(&user.pubkey()),
-            &vec![user],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(init_stake_account));
-
-        Ok(())
+    pub fn init_stake_account(&mut self, user: &Keypair) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::init_stake_account(&self.pool(), &user.pubkey())];
+        Ok(self.execute("init_stake_account", &instructions, user, &[user]))
     }
 
-    pub fn add_stake(&mut self, user: &Keypair, amount: u64) -> Result<(), Box<dyn Error>> {
-        let stake_pool = self.stake_pool_pubkey();
-
-        let stake_account = self.stake_account_pubkey(user);
-        let accounts = AddStake {
-            stake_pool,
-            stake_account,
-            payer: user.pubkey(),
-            stake_pool_vault: self.stake_pool_vault_pubkey(),
-            payer_token_account: get_associated_token_address(
-                &user.pubkey(),
-                &self.vault_token_mint.pubkey(),
-            ),
-            token_program: spl_token::id(),
-        };
-        let mut instructions = self
-            .stake_program_client
-            .request()
-            .accounts(accounts)
-            .args(jet_staking::instruction::AddStake {
-                amount: Amount {
-                    kind: jet_staking::AmountKind::Tokens,
-                    value: amount,
-                },
-            })
-            .instructions()?;
-        instructions.push(self.nonce_instruction());
-        let transaction = Transaction::new_signed_with_payer(
-            &instructions,
-            Some(&user.pubkey()),
-            &vec![user],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
-
-        Ok(())
+    pub fn add_stake(&mut self, user: &Keypair, amount: u64) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::add_stake(&self.pool(), &user.pubkey(), amount)];
+        Ok(self.execute("add_stake", &instructions, user, &[user]))
     }
 
     pub fn unbond_stake_shares(
@@ -331,80 +330,26 @@ impl Framework {
         user: &Keypair,
         unbond_seed: u32,
         share_amount: u64,
-    ) -> Result<(), Box<dyn Error>> {
-        let stake_pool = self.stake_pool_pubkey();
-
-        let stake_account = self.stake_account_pubkey(user);
-        let (unbonding_account, _bump) = Pubkey::find_program_address(
-            &[stake_account.as_ref(), unbond_seed.to_le_bytes().as_ref()],
-            &self.stake_program_client.id(),
-        );
-        let accounts = jet_staking::accounts::UnbondStake {
-            stake_pool,
-            stake_account,
-            payer: user.pubkey(),
-            stake_pool_vault: self.stake_pool_vault_pubkey(),
-            owner: user.pubkey(),
-            unbonding_account,
-            system_program: System::id(),
-        };
-        let transaction = Transaction::new_signed_with_payer(
-            &self
-                .stake_program_client
-                .request()
-                .accounts(accounts)
-                .args(jet_staking::instruction::UnbondStake {
-                    seed: unbond_seed,
-                    amount: Amount {
-                        kind: jet_staking::AmountKind::Shares,
-                        value: share_amount,
-                    },
-                })
-                .instructions()?,
-            Some(&user.pubkey()),
-            &vec![user],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
-
-        Ok(())
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::unbond_stake(
+            &self.pool(),
+            &user.pubkey(),
+            unbond_seed,
+            Amount {
+                kind: jet_staking::AmountKind::Shares,
+                value: share_amount,
+            },
+        )];
+        Ok(self.execute("unbond_stake_shares", &instructions, user, &[user]))
     }
 
     pub fn cancel_unbond(
         &mut self,
         user: &Keypair,
         unbond_seed: u32,
-    ) -> Result<(), Box<dyn Error>> {
-        let stake_pool = self.stake_pool_pubkey();
-
-        let stake_account = self.stake_account_pubkey(user);
-        let (unbonding_account, _bump) = Pubkey::find_program_address(
-            &[stake_account.as_ref(), unbond_seed.to_le_bytes().as_ref()],
-            &self.stake_program_client.id(),
-        );
-        let accounts = jet_staking::accounts::CancelUnbond {
-            stake_pool,
-            stake_account,
-            owner: user.pubkey(),
-            unbonding_account,
-            receiver: user.pubkey(),
-        };
-        let mut instructions = self
-            .stake_program_client
-            .request()
-            .accounts(accounts)
-            .args(jet_staking::instruction::CancelUnbond {})
-            .instructions()?;
-        instructions.push(self.nonce_instruction());
-        let transaction = Transaction::new_signed_with_payer(
-            &instructions,
-            Some(&user.pubkey()),
-            &vec![user],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
-
-        Ok(())
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::cancel_unbond(&self.pool(), &user.pubkey(), unbond_seed)];
+        Ok(self.execute("cancel_unbond", &instructions, user, &[user]))
     }
 
     pub fn unbond_stake_tokens(
@@ -412,162 +357,70 @@ impl Framework {
         user: &Keypair,
         unbond_seed: u32,
         amount: u64,
-    ) -> Result<(), Box<dyn Error>> {
-        let stake_pool = self.stake_pool_pubkey();
-
-        let stake_account = self.stake_account_pubkey(user);
-        let (unbonding_account, _bump) = Pubkey::find_program_address(
-            &[stake_account.as_ref(), unbond_seed.to_le_bytes().as_ref()],
-            &self.stake_program_client.id(),
-        );
-        let accounts = jet_staking::accounts::UnbondStake {
-            stake_pool,
-            stake_account,
-            payer: user.pubkey(),
-            stake_pool_vault: self.stake_pool_vault_pubkey(),
-            owner: user.pubkey(),
-            unbonding_account,
-            system_program: System::id(),
-        };
-        let mut instructions = self
-            .stake_program_client
-            .request()
-            .accounts(accounts)
-            .args(jet_staking::instruction::UnbondStake {
-                seed: unbond_seed,
-                amount: Amount {
-                    kind: jet_staking::AmountKind::Tokens,
-                    value: amount,
-                },
-            })
-            .instructions()?;
-        instructions.push(self.nonce_instruction());
-        let transaction = Transaction::new_signed_with_payer(
-            &instructions,
-            Some(&user.pubkey()),
-            &vec![user],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
-
-        Ok(())
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::unbond_stake(
+            &self.pool(),
+            &user.pubkey(),
+            unbond_seed,
+            Amount {
+                kind: jet_staking::AmountKind::Tokens,
+                value: amount,
+            },
+        )];
+        Ok(self.execute("unbond_stake_tokens", &instructions, user, &[user]))
     }
 
     pub fn withdraw_unbonded_stake(
         &mut self,
         user: &Keypair,
         unbond_seed: u32,
-    ) -> Result<(), Box<dyn Error>> {
-        let stake_pool = self.stake_pool_pubkey();
-
-        let stake_account = self.stake_account_pubkey(user);
-        let (unbonding_account, _bump) = Pubkey::find_program_address(
-            &[stake_account.as_ref(), unbond_seed.to_le_bytes().as_ref()],
-            &self.stake_program_client.id(),
-        );
-        let accounts = jet_staking::accounts::WithdrawUnbonded {
-            stake_pool,
-            stake_account,
-            stake_pool_vault: self.stake_pool_vault_pubkey(),
-            owner: user.pubkey(),
-            unbonding_account,
-            closer: user.pubkey(),
-            token_receiver: get_associated_token_address(
-                &user.pubkey(),
-                &self.vault_token_mint.pubkey(),
-            ),
-            token_program: spl_token::id(),
-        };
-        let mut instructions = self
-            .stake_program_client
-            .request()
-            .accounts(accounts)
-            .args(jet_staking::instruction::WithdrawUnbonded {})
-            .instructions()?;
-        instructions.push(self.nonce_instruction());
-        let transaction = Transaction::new_signed_with_payer(
-            &instructions,
-            Some(&user.pubkey()),
-            &vec![user],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
-
-        Ok(())
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::withdraw_unbonded(
+            &self.pool(),
+            &user.pubkey(),
+            unbond_seed,
+        )];
+        Ok(self.execute("withdraw_unbonded_stake", &instructions, user, &[user]))
     }
 
-    pub fn withdraw_bonded(&mut self, user: &Keypair, amount: u64) -> Result<(), Box<dyn Error>> {
-        let stake_pool = self.stake_pool_pubkey();
-
-        let accounts = jet_staking::accounts::WithdrawBonded {
-            stake_pool,
-            stake_pool_vault: self.stake_pool_vault_pubkey(),
-            token_receiver: get_associated_token_address(
-                &user.pubkey(),
-                &self.vault_token_mint.pubkey(),
-            ),
-            token_program: spl_token::id(),
-            authority: self.pool_authority.pubkey(),
-        };
-        let mut instructions = self
-            .stake_program_client
-            .request()
-            .accounts(accounts)
-            .args(jet_staking::instruction::WithdrawBonded { amount })
-            .instructions()?;
-        instructions.push(self.nonce_instruction());
-        let transaction = Transaction::new_signed_with_payer(
+    pub fn withdraw_bonded(
+        &mut self,
+        user: &Keypair,
+        amount: u64,
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::withdraw_bonded(&self.pool(), &user.pubkey(), amount)];
+        let pool_authority = clone_keypair(&self.pool_authority);
+        Ok(self.execute(
+            "withdraw_bonded",
             &instructions,
-            Some(&user.pubkey()),
-            &vec![user, &self.pool_authority],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
-
-        Ok(())
+            user,
+            &[user, &pool_authority],
+        ))
     }
 
-    pub fn mint_votes(&mut self, user: &Keypair, amount: u64) -> Result<(), Box<dyn Error>> {
-        let stake_pool = self.stake_pool_pubkey();
+    pub fn mint_votes(&mut self, user: &Keypair, amount: u64) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::mint_votes(&self.pool(), &user.pubkey(), amount)];
+        Ok(self.execute("mint_votes", &instructions, user, &[user]))
+    }
 
-        let stake_account = self.stake_account_pubkey(user);
-        let accounts = jet_staking::accounts::MintVotes {
-            owner: user.pubkey(),
-            stake_vote_mint: self.stake_vote_mint_pubkey(),
-            voter_token_account: get_associated_token_address(
-                &user.pubkey(),
-                &self.stake_vote_mint_pubkey(),
-            ),
-            stake_pool,
-            stake_account,
-            stake_pool_vault: self.stake_pool_vault_pubkey(),
-            token_program: spl_token::id(),
-        };
-        let transaction = Transaction::new_signed_with_payer(
-            &self
-                .stake_program_client
-                .request()
-                .accounts(accounts)
-                .args(jet_staking::instruction::MintVotes {
-                    amount: Amount {
-                        kind: jet_staking::AmountKind::Tokens,
-                        value: amount,
-                    },
-                })
-                .instructions()?,
-            Some(&user.pubkey()),
-            &vec![user],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
+    pub fn burn_votes(
+        &mut self,
+        user: &Keypair,
+        amount: Option<u64>,
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::burn_votes(&self.pool(), &user.pubkey(), amount)];
+        Ok(self.execute("burn_votes", &instructions, user, &[user]))
+    }
 
-        Ok(())
+    pub fn close_stake_account(&mut self, user: &Keypair) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::close_stake_account(&self.pool(), &user.pubkey())];
+        Ok(self.execute("close_stake_account", &instructions, user, &[user]))
     }
 
     pub fn award_pubkey(&self, stake_account: Pubkey, seed: String) -> Pubkey {
         let (award, _bump) = Pubkey::find_program_address(
             &[stake_account.as_ref(), seed.as_bytes()],
-            &self.rewards_program_client.id(),
+            &jet_rewards::id(),
         );
 
         award
@@ -575,7 +428,7 @@ impl Framework {
 
     pub fn distribution_pubkey(&self, seed: String) -> Pubkey {
         let (distribution, _bump) =
-            Pubkey::find_program_address(&[seed.as_bytes()], &self.rewards_program_client.id());
+            Pubkey::find_program_address(&[seed.as_bytes()], &jet_rewards::id());
 
         distribution
     }
@@ -583,7 +436,7 @@ impl Framework {
     pub fn reward_vault_pubkey(&self, account: Pubkey, _seed: String) -> Pubkey {
         let (award_vault, _bump) = Pubkey::find_program_address(
             &[account.as_ref(), b"vault".as_ref()],
-            &self.rewards_program_client.id(),
+            &jet_rewards::id(),
         );
 
         award_vault
@@ -597,87 +450,63 @@ impl Framework {
         end_at: u64,
         amount: u64,
         seed: String,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<TxOutcome, Box<dyn Error>> {
         let stake_account = self.stake_account_pubkey(receiver);
         let award = self.award_pubkey(stake_account, seed.clone());
         let vault = self.reward_vault_pubkey(award, seed.clone());
-        println!("award: {}, vault: {}, award seed: {}", award, vault, seed);
-        let accounts = jet_rewards::accounts::AwardCreate {
-            system_program: System::id(),
-            award,
-            vault,
-            token_mint: self.vault_token_mint.pubkey(),
-            token_source: get_associated_token_address(
-                &creator.pubkey(),
-                &self.vault_token_mint.pubkey(),
-            ),
-            token_source_authority: creator.pubkey(),
-            payer_rent: creator.pubkey(),
-            token_program: spl_token::id(),
-            rent: solana_program::sysvar::rent::id(),
-        };
-        let transaction = Transaction::new_signed_with_payer(
-            &self
-                .rewards_program_client
-                .request()
-                .accounts(accounts)
-                .args(jet_rewards::instruction::AwardCreate {
-                    params: jet_rewards::AwardCreateParams {
-                        seed,
-                        authority: creator.pubkey(),
-                        stake_account,
-                        amount,
-                        begin_at,
-                        end_at,
-                    },
-                })
-                .instructions()?,
-            Some(&creator.pubkey()),
-            &vec![creator],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
-
-        Ok(())
+        if !self.quiet {
+            outln!("award: {}, vault: {}, award seed: {}", award, vault, seed);
+        }
+        let instructions = vec![ix::award_create(
+            &self.pool(),
+            &creator.pubkey(),
+            &receiver.pubkey(),
+            begin_at,
+            end_at,
+            amount,
+            &seed,
+        )];
+        Ok(self.execute("create_award", &instructions, creator, &[creator]))
     }
 
     pub fn release_award(
         &mut self,
         receiver: &Keypair,
         seed: String,
-    ) -> Result<(), Box<dyn Error>> {
-        let stake_account = self.stake_account_pubkey(receiver);
-        let stake_pool = self.stake_pool_pubkey();
-        let stake_pool_vault = self.stake_pool_vault_pubkey();
-        let award = self.award_pubkey(stake_account, seed.clone());
-        let vault = self.reward_vault_pubkey(award, seed);
-        let accounts = jet_rewards::accounts::AwardRelease {
-            award,
-            vault,
-            token_program: spl_token::id(),
-            stake_account,
-            stake_pool,
-            stake_pool_vault,
-            staking_program: jet_staking::id(),
-        };
-
-        let mut instructions = self
-            .rewards_program_client
-            .request()
-            .accounts(accounts)
-            .args(jet_rewards::instruction::AwardRelease {})
-            .instructions()?;
-        instructions.push(self.nonce_instruction());
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::award_release(&self.pool(), &receiver.pubkey(), &seed)];
+        Ok(self.execute("release_award", &instructions, receiver, &[receiver]))
+    }
 
-        let transaction = Transaction::new_signed_with_payer(
-            &instructions,
-            Some(&receiver.pubkey()),
-            &vec![receiver],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
+    /// returns the unreleased tokens of the award to `creator`'s token account
+    pub fn revoke_award(
+        &mut self,
+        creator: &Keypair,
+        receiver: &Keypair,
+        seed: String,
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::award_revoke(
+            &self.pool(),
+            &creator.pubkey(),
+            &receiver.pubkey(),
+            &seed,
+        )];
+        Ok(self.execute("revoke_award", &instructions, creator, &[creator]))
+    }
 
-        Ok(())
+    pub fn close_award(
+        &mut self,
+        creator: &Keypair,
+        receiver: &Keypair,
+        seed: String,
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::award_close(
+            &self.pool(),
+            &creator.pubkey(),
+            &receiver.pubkey(),
+            &seed,
+        )];
+        Ok(self.execute("close_award", &instructions, creator, &[creator]))
     }
 
     pub fn create_distribution(
@@ -688,136 +517,77 @@ impl Framework {
         end_at: u64,
         amount: u64,
         seed: String,
-    ) -> Result<(), Box<dyn Error>> {
-        let distribution = self.distribution_pubkey(seed.clone());
-        let vault = self.reward_vault_pubkey(distribution, seed.clone());
-        let accounts = jet_rewards::accounts::DistributionCreate {
-            system_program: System::id(),
-            distribution,
-            vault,
-            token_mint: self.vault_token_mint.pubkey(),
-            payer_rent: creator.pubkey(),
-            token_program: spl_token::id(),
-            rent: solana_program::sysvar::rent::id(),
-            payer_token_authority: creator.pubkey(),
-            payer_token_account: get_associated_token_address(
-                &creator.pubkey(),
-                &self.vault_token_mint.pubkey(),
-            ),
-        };
-        let transaction = Transaction::new_signed_with_payer(
-            &self
-                .rewards_program_client
-                .request()
-                .accounts(accounts)
-                .args(jet_rewards::instruction::DistributionCreate {
-                    params: jet_rewards::DistributionCreateParams {
-                        seed,
-                        authority: creator.pubkey(),
-                        amount,
-                        begin_at,
-                        end_at,
-                        target_account: get_associated_token_address(
-                            &receiver.pubkey(),
-                            &self.vault_token_mint.pubkey(),
-                        ),
-                    },
-                })
-                .instructions()?,
-            Some(&creator.pubkey()),
-            &vec![creator],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
-
-        Ok(())
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::distribution_create(
+            &self.pool(),
+            &creator.pubkey(),
+            &receiver.pubkey(),
+            begin_at,
+            end_at,
+            amount,
+            &seed,
+        )];
+        Ok(self.execute("create_distribution", &instructions, creator, &[creator]))
     }
 
     pub fn release_distribution(
         &mut self,
         receiver: &Keypair,
         seed: String,
-    ) -> Result<(), Box<dyn Error>> {
-        let distribution = self.distribution_pubkey(seed.clone());
-        let vault = self.reward_vault_pubkey(distribution, seed);
-        let accounts = jet_rewards::accounts::DistributionRelease {
-            distribution,
-            vault,
-            token_program: spl_token::id(),
-            target_account: get_associated_token_address(
-                &receiver.pubkey(),
-                &self.vault_token_mint.pubkey(),
-            ),
-        };
-
-        let mut instructions = self
-            .rewards_program_client
-            .request()
-            .accounts(accounts)
-            .args(jet_rewards::instruction::DistributionRelease {})
-            .instructions()?;
-        instructions.push(self.nonce_instruction());
-
-        let transaction = Transaction::new_signed_with_payer(
-            &instructions,
-            Some(&receiver.pubkey()),
-            &vec![receiver],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::distribution_release(
+            &self.pool(),
+            &receiver.pubkey(),
+            &seed,
+        )];
+        Ok(self.execute("release_distribution", &instructions, receiver, &[receiver]))
+    }
 
-        Ok(())
+    pub fn close_distribution(
+        &mut self,
+        creator: &Keypair,
+        seed: String,
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::distribution_close(&creator.pubkey(), &seed)];
+        Ok(self.execute("close_distribution", &instructions, creator, &[creator]))
     }
 
     pub fn create_airdrop(
         &mut self,
         airdrop: &Keypair,
         expire_at: i64,
-    ) -> Result<(), Box<dyn Error>> {
-        let accounts = jet_rewards::accounts::AirdropCreate {
-            system_program: System::id(),
-            token_mint: self.vault_token_mint.pubkey(),
-            token_program: spl_token::id(),
-            rent: solana_program::sysvar::rent::id(),
-            airdrop: airdrop.pubkey(),
-            authority: self.pool_authority.pubkey(),
-            reward_vault: self.reward_vault_pubkey(airdrop.pubkey(), "".to_string()),
-            payer: self.pool_authority.pubkey(),
-        };
-        let mut instructions = vec![];
-
-        let airdrop_account_size = 8 + std::mem::size_of::<jet_rewards::state::Airdrop>();
-        instructions.push(solana_program::system_instruction::create_account(
-            &self.pool_authority.pubkey(),
-            &airdrop.pubkey(),
-            self.env.get_rent_excemption(airdrop_account_size),
-            airdrop_account_size as u64,
-            &jet_rewards::id(),
-        ));
-        instructions.extend(
-            self.rewards_program_client
-                .request()
-                .accounts(accounts)
-                .args(jet_rewards::instruction::AirdropCreate {
-                    params: jet_rewards::AirdropCreateParams {
-                        expire_at,
-                        stake_pool: self.stake_pool_pubkey(),
-                        short_desc: "sdhdfshdfshdfhdfdfhhdf".to_string(),
-                        // flags are unused rn
-                        flags: 0,
-                    },
-                })
-                .instructions()?,
-        );
-        let transaction = Transaction::new_signed_with_payer(
-            &instructions,
-            Some(&self.pool_authority.pubkey()),
-            &vec![&self.pool_authority, airdrop],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        self.create_airdrop_with(airdrop, expire_at, "sdhdfshdfshdfhdfdfhhdf", 0)
+    }
 
-        Ok(())
+    pub fn create_airdrop_with(
+        &mut self,
+        airdrop: &Keypair,
+        expire_at: i64,
+        short_desc: &str,
+        flags: u64,
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![
+            ix::create_airdrop_account(
+                &self.pool_authority.pubkey(),
+                &airdrop.pubkey(),
+                self.env.get_rent_excemption(ix::airdrop_account_size()),
+            ),
+            ix::airdrop_create_with(
+                &self.pool(),
+                &airdrop.pubkey(),
+                expire_at,
+                short_desc,
+                flags,
+            ),
+        ];
+        let pool_authority = clone_keypair(&self.pool_authority);
+        Ok(self.execute(
+            "create_airdrop",
+            &instructions,
+            &pool_authority,
+            &[&pool_authority, airdrop],
+        ))
     }
 
     pub fn airdrop_add_recipients(
@@ -825,122 +595,74 @@ impl Framework {
         recipients: Vec<jet_rewards::AirdropRecipientParam>,
         airdrop: Pubkey,
         start_index: u64,
-    ) -> Result<(), Box<dyn Error>> {
-        let accounts = jet_rewards::accounts::AirdropAddRecipients {
-            airdrop,
-            authority: self.pool_authority.pubkey(),
-        };
-
-        let mut instructions = self
-            .rewards_program_client
-            .request()
-            .accounts(accounts)
-            .args(jet_rewards::instruction::AirdropAddRecipients {
-                params: jet_rewards::AirdropAddRecipientsParams {
-                    start_index,
-                    recipients,
-                },
-            })
-            .instructions()?;
-        instructions.push(self.nonce_instruction());
-
-        let transaction = Transaction::new_signed_with_payer(
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::airdrop_add_recipients(
+            &self.pool(),
+            &airdrop,
+            start_index,
+            recipients,
+        )];
+        let pool_authority = clone_keypair(&self.pool_authority);
+        Ok(self.execute(
+            "airdrop_add_recipients",
             &instructions,
-            Some(&self.pool_authority.pubkey()),
-            &vec![&self.pool_authority],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
-
-        Ok(())
+            &pool_authority,
+            &[&pool_authority],
+        ))
     }
 
-    pub fn airdrop_finalize(&mut self, airdrop: Pubkey) -> Result<(), Box<dyn Error>> {
-        let accounts = jet_rewards::accounts::AirdropFinalize {
-            airdrop,
-            authority: self.pool_authority.pubkey(),
-            reward_vault: self.reward_vault_pubkey(airdrop, "".to_string()),
-        };
-
-        let mut instructions = self
-            .rewards_program_client
-            .request()
-            .accounts(accounts)
-            .args(jet_rewards::instruction::AirdropFinalize {})
-            .instructions()?;
-        instructions.push(self.nonce_instruction());
-
-        let transaction = Transaction::new_signed_with_payer(
+    pub fn airdrop_finalize(&mut self, airdrop: Pubkey) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::airdrop_finalize(&self.pool(), &airdrop)];
+        let pool_authority = clone_keypair(&self.pool_authority);
+        Ok(self.execute(
+            "airdrop_finalize",
             &instructions,
-            Some(&self.pool_authority.pubkey()),
-            &vec![&self.pool_authority],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
-
-        Ok(())
+            &pool_authority,
+            &[&pool_authority],
+        ))
     }
 
     pub fn airdrop_claim(
         &mut self,
         recipient: &Keypair,
         airdrop: Pubkey,
-    ) -> Result<(), Box<dyn Error>> {
-        let accounts = jet_rewards::accounts::AirdropClaim {
-            airdrop,
-            reward_vault: self.reward_vault_pubkey(airdrop, "".to_string()),
-            recipient: recipient.pubkey(),
-            // receiver is unused dunno why its there tbh
-            receiver: recipient.pubkey(),
-            stake_pool: self.stake_pool_pubkey(),
-            stake_pool_vault: self.stake_pool_vault_pubkey(),
-            stake_account: self.stake_account_pubkey(recipient),
-            staking_program: jet_staking::id(),
-            token_program: spl_token::id(),
-        };
-
-        let mut instructions = self
-            .rewards_program_client
-            .request()
-            .accounts(accounts)
-            .args(jet_rewards::instruction::AirdropClaim {})
-            .instructions()?;
-        instructions.push(self.nonce_instruction());
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::airdrop_claim(
+            &self.pool(),
+            &airdrop,
+            &recipient.pubkey(),
+        )];
+        Ok(self.execute("airdrop_claim", &instructions, recipient, &[recipient]))
+    }
 
-        let transaction = Transaction::new_signed_with_payer(
+    pub fn airdrop_close(&mut self, airdrop: Pubkey) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![ix::airdrop_close(&self.pool(), &airdrop)];
+        let pool_authority = clone_keypair(&self.pool_authority);
+        Ok(self.execute(
+            "airdrop_close",
             &instructions,
-            Some(&recipient.pubkey()),
-            &vec![recipient],
-            self.env.get_recent_blockhash(),
-        );
-        Framework::process_tx_result(self.env.execute_transaction(transaction));
-
-        Ok(())
+            &pool_authority,
+            &[&pool_authority],
+        ))
     }
+
     pub fn mint_tokens(
         &mut self,
         mint: Pubkey,
         authority: &Keypair,
         destination: Pubkey,
         amount: u64,
-    ) -> Result<(), Box<dyn Error>> {
-        let instructions = vec![
-            spl_token::instruction::mint_to(
-                &spl_token::id(),
-                &mint,
-                &destination,
-                &authority.pubkey(),
-                &[],
-                amount,
-            )?,
-            self.nonce_instruction(),
-        ];
-        Framework::process_tx_result(
-            self.env
-                .execute_as_transaction(&*instructions, &[authority]),
-        );
-
-        Ok(())
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let instructions = vec![spl_token::instruction::mint_to(
+            &spl_token::id(),
+            &mint,
+            &destination,
+            &authority.pubkey(),
+            &[],
+            amount,
+        )?];
+        let payer = self.env.payer();
+        Ok(self.execute("mint_tokens", &instructions, &payer, &[&payer, authority]))
     }
 
     pub fn mint_vault_token(&mut self, user: &Keypair, amount: u64) -> Result<(), Box<dyn Error>> {
@@ -949,7 +671,7 @@ impl Framework {
             .create_associated_token_account(user, self.vault_token_mint.pubkey());
         self.env.mint_tokens(
             self.vault_token_mint.pubkey(),
-            &self.pool_authority,
+            &self.vault_mint_authority,
             account,
             amount,
         );
@@ -957,22 +679,94 @@ impl Framework {
         Ok(())
     }
 
-    fn nonce_instruction(&mut self) -> Instruction {
-        let instruction = Instruction::new_with_bytes(
-            self.nop_program_pubkey,
-            &self.tx_nonce.to_le_bytes(),
-            vec![],
-        );
-        self.tx_nonce += 1;
+    /// freezes a vault token account, needs the framework to be built with a freeze authority
+    pub fn freeze_token_account(&mut self, account: Pubkey) -> Result<TxOutcome, Box<dyn Error>> {
+        let authority = self.freeze_authority()?;
+        let instructions = vec![spl_token::instruction::freeze_account(
+            &spl_token::id(),
+            &account,
+            &self.vault_token_mint.pubkey(),
+            &authority.pubkey(),
+            &[],
+        )?];
+        let payer = self.env.payer();
+        Ok(self.execute(
+            "freeze_token_account",
+            &instructions,
+            &payer,
+            &[&payer, &authority],
+        ))
+    }
+
+    pub fn thaw_token_account(&mut self, account: Pubkey) -> Result<TxOutcome, Box<dyn Error>> {
+        let authority = self.freeze_authority()?;
+        let instructions = vec![spl_token::instruction::thaw_account(
+            &spl_token::id(),
+            &account,
+            &self.vault_token_mint.pubkey(),
+            &authority.pubkey(),
+            &[],
+        )?];
+        let payer = self.env.payer();
+        Ok(self.execute(
+            "thaw_token_account",
+            &instructions,
+            &payer,
+            &[&payer, &authority],
+        ))
+    }
 
-        instruction
+    fn freeze_authority(&self) -> Result<Keypair, Box<dyn Error>> {
+        Ok(clone_keypair(self.vault_freeze_authority.as_ref().ok_or(
+            "vault token mint has no freeze authority, set one with Framework::builder()",
+        )?))
+    }
+
+    /// the blockhash to sign the next transaction with, see `unique_transactions`
+    fn next_blockhash(&mut self) -> Hash {
+        if self.unique_transactions {
+            advance_blockhash(&mut self.env)
+        } else {
+            self.env.get_recent_blockhash()
+        }
     }
 }
 
+/// ticks the bank until it registers a new blockhash, which `LocalEnvironment` can't do itself
+pub fn advance_blockhash(env: &mut LocalEnvironment) -> Hash {
+    let bank = env.bank();
+    let blockhash = bank.last_blockhash();
+    while bank.last_blockhash() == blockhash {
+        bank.register_tick(&Hash::new_unique());
+    }
+
+    env.get_recent_blockhash()
+}
+
 pub fn clone_keypair(keypair: &Keypair) -> Keypair {
     Keypair::from_bytes(&keypair.to_bytes()).unwrap()
 }
 
+pub fn is_jet_program(program: &Pubkey) -> bool {
+    *program == jet_auth::id() || *program == jet_staking::id() || *program == jet_rewards::id()
+}
+
+pub fn program_name(program: &Pubkey) -> String {
+    if *program == jet_auth::id() {
+        "jet_auth".to_string()
+    } else if *program == jet_staking::id() {
+        "jet_staking".to_string()
+    } else if *program == jet_rewards::id() {
+        "jet_rewards".to_string()
+    } else if *program == spl_token::id() {
+        "spl_token".to_string()
+    } else if *program == System::id() {
+        "system".to_string()
+    } else {
+        program.to_string()
+    }
+}
+
 pub fn get_balance(
     test_env: &Framework,
     user: &Keypair,
@@ -987,3 +781,31 @@ pub fn get_balance(
     )?
     .amount)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poc_framework_osec::solana_sdk::system_instruction::transfer;
+
+    #[test]
+    fn identical_instructions_get_different_signatures() {
+        let mut env = LocalEnvironment::new();
+        let payer = env.payer();
+        let instruction = transfer(&payer.pubkey(), &Keypair::new().pubkey(), 1_000_000);
+
+        let mut signatures = vec![];
+        for _ in 0..2 {
+            let transaction = Transaction::new_signed_with_payer(
+                &[instruction.clone()],
+                Some(&payer.pubkey()),
+                &[&payer],
+                advance_blockhash(&mut env),
+            );
+            signatures.push(transaction.signatures[0]);
+            let result = env.execute_transaction(transaction);
+            assert!(result.transaction.meta.unwrap().status.is_ok());
+        }
+
+        assert_ne!(signatures[0], signatures[1]);
+    }
+}
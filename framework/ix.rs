@@ -0,0 +1,574 @@
+//! Pure instruction builders for every jet instruction the framework wraps.
+//!
+//! Nothing in here touches the environment, so a POC can build an instruction, tamper with its
+//! account metas or data, and then send it with a [`super::tx_builder::TxBuilder`].
+
+use anchor_lang::{
+    solana_program::{self, instruction::Instruction, pubkey::Pubkey, system_program},
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::associated_token::get_associated_token_address;
+use jet_staking::{Amount, AmountKind};
+
+/// the addresses derived from a stake pool's seed
+#[derive(Clone, Debug)]
+pub struct Pool {
+    pub seed: String,
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+}
+
+impl Pool {
+    pub fn stake_pool(&self) -> Pubkey {
+        Pubkey::find_program_address(&[self.seed.as_bytes()], &jet_staking::id()).0
+    }
+
+    pub fn vault(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[self.seed.as_bytes(), b"vault".as_ref()],
+            &jet_staking::id(),
+        )
+        .0
+    }
+
+    pub fn vote_mint(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[self.seed.as_bytes(), b"vote-mint".as_ref()],
+            &jet_staking::id(),
+        )
+        .0
+    }
+
+    pub fn collateral_mint(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[self.seed.as_bytes(), b"collateral-mint".as_ref()],
+            &jet_staking::id(),
+        )
+        .0
+    }
+
+    pub fn stake_account(&self, owner: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[self.stake_pool().as_ref(), owner.as_ref()],
+            &jet_staking::id(),
+        )
+        .0
+    }
+
+    pub fn unbonding_account(&self, owner: &Pubkey, unbond_seed: u32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                self.stake_account(owner).as_ref(),
+                unbond_seed.to_le_bytes().as_ref(),
+            ],
+            &jet_staking::id(),
+        )
+        .0
+    }
+
+    /// the owner's associated token account for the staked token
+    pub fn token_account(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address(owner, &self.token_mint)
+    }
+}
+
+pub fn user_auth(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[user.as_ref()], &jet_auth::id()).0
+}
+
+pub fn award(stake_account: &Pubkey, seed: &str) -> Pubkey {
+    Pubkey::find_program_address(
+        &[stake_account.as_ref(), seed.as_bytes()],
+        &jet_rewards::id(),
+    )
+    .0
+}
+
+pub fn distribution(seed: &str) -> Pubkey {
+    Pubkey::find_program_address(&[seed.as_bytes()], &jet_rewards::id()).0
+}
+
+/// the token vault of an award, distribution or airdrop
+pub fn reward_vault(account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[account.as_ref(), b"vault".as_ref()], &jet_rewards::id()).0
+}
+
+fn instruction(
+    program_id: Pubkey,
+    accounts: impl ToAccountMetas,
+    args: impl InstructionData,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: accounts.to_account_metas(None),
+        data: args.data(),
+    }
+}
+
+pub fn create_user_auth(user: &Pubkey) -> Instruction {
+    instruction(
+        jet_auth::id(),
+        jet_auth::accounts::CreateUserAuthentication {
+            user: *user,
+            payer: *user,
+            auth: user_auth(user),
+            system_program: system_program::id(),
+        },
+        jet_auth::instruction::CreateUserAuth {},
+    )
+}
+
+pub fn authenticate(user: &Pubkey, authority: &Pubkey) -> Instruction {
+    instruction(
+        jet_auth::id(),
+        jet_auth::accounts::Authenticate {
+            auth: user_auth(user),
+            authority: *authority,
+        },
+        jet_auth::instruction::Authenticate {},
+    )
+}
+
+pub fn init_pool(pool: &Pool, unbond_period: u64) -> Instruction {
+    instruction(
+        jet_staking::id(),
+        jet_staking::accounts::InitPool {
+            payer: pool.authority,
+            authority: pool.authority,
+            token_mint: pool.token_mint,
+            stake_pool: pool.stake_pool(),
+            stake_vote_mint: pool.vote_mint(),
+            stake_collateral_mint: pool.collateral_mint(),
+            stake_pool_vault: pool.vault(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+            rent: solana_program::sysvar::rent::id(),
+        },
+        jet_staking::instruction::InitPool {
+            seed: pool.seed.clone(),
+            config: jet_staking::instructions::PoolConfig { unbond_period },
+        },
+    )
+}
+
+pub fn init_stake_account(pool: &Pool, owner: &Pubkey) -> Instruction {
+    instruction(
+        jet_staking::id(),
+        jet_staking::accounts::InitStakeAccount {
+            owner: *owner,
+            auth: user_auth(owner),
+            stake_pool: pool.stake_pool(),
+            stake_account: pool.stake_account(owner),
+            payer: *owner,
+            system_program: system_program::id(),
+        },
+        jet_staking::instruction::InitStakeAccount {},
+    )
+}
+
+pub fn add_stake(pool: &Pool, owner: &Pubkey, amount: u64) -> Instruction {
+    instruction(
+        jet_staking::id(),
+        jet_staking::accounts::AddStake {
+            stake_pool: pool.stake_pool(),
+            stake_account: pool.stake_account(owner),
+            payer: *owner,
+            stake_pool_vault: pool.vault(),
+            payer_token_account: pool.token_account(owner),
+            token_program: spl_token::id(),
+        },
+        jet_staking::instruction::AddStake {
+            amount: Amount {
+                kind: AmountKind::Tokens,
+                value: amount,
+            },
+        },
+    )
+}
+
+pub fn unbond_stake(pool: &Pool, owner: &Pubkey, unbond_seed: u32, amount: Amount) -> Instruction {
+    instruction(
+        jet_staking::id(),
+        jet_staking::accounts::UnbondStake {
+            stake_pool: pool.stake_pool(),
+            stake_account: pool.stake_account(owner),
+            payer: *owner,
+            stake_pool_vault: pool.vault(),
+            owner: *owner,
+            unbonding_account: pool.unbonding_account(owner, unbond_seed),
+            system_program: system_program::id(),
+        },
+        jet_staking::instruction::UnbondStake {
+            seed: unbond_seed,
+            amount,
+        },
+    )
+}
+
+pub fn cancel_unbond(pool: &Pool, owner: &Pubkey, unbond_seed: u32) -> Instruction {
+    instruction(
+        jet_staking::id(),
+        jet_staking::accounts::CancelUnbond {
+            stake_pool: pool.stake_pool(),
+            stake_account: pool.stake_account(owner),
+            owner: *owner,
+            unbonding_account: pool.unbonding_account(owner, unbond_seed),
+            receiver: *owner,
+        },
+        jet_staking::instruction::CancelUnbond {},
+    )
+}
+
+pub fn withdraw_unbonded(pool: &Pool, owner: &Pubkey, unbond_seed: u32) -> Instruction {
+    instruction(
+        jet_staking::id(),
+        jet_staking::accounts::WithdrawUnbonded {
+            stake_pool: pool.stake_pool(),
+            stake_account: pool.stake_account(owner),
+            stake_pool_vault: pool.vault(),
+            owner: *owner,
+            unbonding_account: pool.unbonding_account(owner, unbond_seed),
+            closer: *owner,
+            token_receiver: pool.token_account(owner),
+            token_program: spl_token::id(),
+        },
+        jet_staking::instruction::WithdrawUnbonded {},
+    )
+}
+
+pub fn withdraw_bonded(pool: &Pool, receiver: &Pubkey, amount: u64) -> Instruction {
+    instruction(
+        jet_staking::id(),
+        jet_staking::accounts::WithdrawBonded {
+            stake_pool: pool.stake_pool(),
+            stake_pool_vault: pool.vault(),
+            token_receiver: pool.token_account(receiver),
+            token_program: spl_token::id(),
+            authority: pool.authority,
+        },
+        jet_staking::instruction::WithdrawBonded { amount },
+    )
+}
+
+pub fn mint_votes(pool: &Pool, owner: &Pubkey, amount: u64) -> Instruction {
+    instruction(
+        jet_staking::id(),
+        jet_staking::accounts::MintVotes {
+            owner: *owner,
+            stake_vote_mint: pool.vote_mint(),
+            voter_token_account: get_associated_token_address(owner, &pool.vote_mint()),
+            stake_pool: pool.stake_pool(),
+            stake_account: pool.stake_account(owner),
+            stake_pool_vault: pool.vault(),
+            token_program: spl_token::id(),
+        },
+        jet_staking::instruction::MintVotes {
+            amount: Amount {
+                kind: AmountKind::Tokens,
+                value: amount,
+            },
+        },
+    )
+}
+
+/// burns `amount` of the owner's vote tokens, all of them if `None`
+pub fn burn_votes(pool: &Pool, owner: &Pubkey, amount: Option<u64>) -> Instruction {
+    instruction(
+        jet_staking::id(),
+        jet_staking::accounts::BurnVotes {
+            owner: *owner,
+            stake_pool: pool.stake_pool(),
+            stake_account: pool.stake_account(owner),
+            stake_vote_mint: pool.vote_mint(),
+            voter_token_account: get_associated_token_address(owner, &pool.vote_mint()),
+            voter: *owner,
+            token_program: spl_token::id(),
+        },
+        jet_staking::instruction::BurnVotes { amount },
+    )
+}
+
+/// closes the owner's stake account, the rent goes to the owner
+pub fn close_stake_account(pool: &Pool, owner: &Pubkey) -> Instruction {
+    instruction(
+        jet_staking::id(),
+        jet_staking::accounts::CloseStakeAccount {
+            owner: *owner,
+            closer: *owner,
+            stake_account: pool.stake_account(owner),
+        },
+        jet_staking::instruction::CloseStakeAccount {},
+    )
+}
+
+pub fn award_create(
+    pool: &Pool,
+    creator: &Pubkey,
+    receiver: &Pubkey,
+    begin_at: u64,
+    end_at: u64,
+    amount: u64,
+    seed: &str,
+) -> Instruction {
+    let stake_account = pool.stake_account(receiver);
+    let award = award(&stake_account, seed);
+    instruction(
+        jet_rewards::id(),
+        jet_rewards::accounts::AwardCreate {
+            system_program: system_program::id(),
+            award,
+            vault: reward_vault(&award),
+            token_mint: pool.token_mint,
+            token_source: pool.token_account(creator),
+            token_source_authority: *creator,
+            payer_rent: *creator,
+            token_program: spl_token::id(),
+            rent: solana_program::sysvar::rent::id(),
+        },
+        jet_rewards::instruction::AwardCreate {
+            params: jet_rewards::AwardCreateParams {
+                seed: seed.to_string(),
+                authority: *creator,
+                stake_account,
+                amount,
+                begin_at,
+                end_at,
+            },
+        },
+    )
+}
+
+pub fn award_release(pool: &Pool, receiver: &Pubkey, seed: &str) -> Instruction {
+    let stake_account = pool.stake_account(receiver);
+    let award = award(&stake_account, seed);
+    instruction(
+        jet_rewards::id(),
+        jet_rewards::accounts::AwardRelease {
+            award,
+            vault: reward_vault(&award),
+            token_program: spl_token::id(),
+            stake_account,
+            stake_pool: pool.stake_pool(),
+            stake_pool_vault: pool.vault(),
+            staking_program: jet_staking::id(),
+        },
+        jet_rewards::instruction::AwardRelease {},
+    )
+}
+
+/// takes back the unreleased tokens of an award, `authority` being its creator
+pub fn award_revoke(pool: &Pool, authority: &Pubkey, receiver: &Pubkey, seed: &str) -> Instruction {
+    let award = award(&pool.stake_account(receiver), seed);
+    instruction(
+        jet_rewards::id(),
+        jet_rewards::accounts::AwardRevoke {
+            award,
+            vault: reward_vault(&award),
+            authority: *authority,
+            receiver: *authority,
+            token_receiver: pool.token_account(authority),
+            token_program: spl_token::id(),
+        },
+        jet_rewards::instruction::AwardRevoke {},
+    )
+}
+
+/// closes the award and its vault, sending the rent to `authority`
+pub fn award_close(pool: &Pool, authority: &Pubkey, receiver: &Pubkey, seed: &str) -> Instruction {
+    let award = award(&pool.stake_account(receiver), seed);
+    instruction(
+        jet_rewards::id(),
+        jet_rewards::accounts::AwardClose {
+            award,
+            vault: reward_vault(&award),
+            receiver: *authority,
+            authority: *authority,
+            token_program: spl_token::id(),
+        },
+        jet_rewards::instruction::AwardClose {},
+    )
+}
+
+pub fn distribution_create(
+    pool: &Pool,
+    creator: &Pubkey,
+    receiver: &Pubkey,
+    begin_at: u64,
+    end_at: u64,
+    amount: u64,
+    seed: &str,
+) -> Instruction {
+    let distribution = distribution(seed);
+    instruction(
+        jet_rewards::id(),
+        jet_rewards::accounts::DistributionCreate {
+            system_program: system_program::id(),
+            distribution,
+            vault: reward_vault(&distribution),
+            token_mint: pool.token_mint,
+            payer_rent: *creator,
+            token_program: spl_token::id(),
+            rent: solana_program::sysvar::rent::id(),
+            payer_token_authority: *creator,
+            payer_token_account: pool.token_account(creator),
+        },
+        jet_rewards::instruction::DistributionCreate {
+            params: jet_rewards::DistributionCreateParams {
+                seed: seed.to_string(),
+                authority: *creator,
+                amount,
+                begin_at,
+                end_at,
+                target_account: pool.token_account(receiver),
+            },
+        },
+    )
+}
+
+pub fn distribution_release(pool: &Pool, receiver: &Pubkey, seed: &str) -> Instruction {
+    let distribution = distribution(seed);
+    instruction(
+        jet_rewards::id(),
+        jet_rewards::accounts::DistributionRelease {
+            distribution,
+            vault: reward_vault(&distribution),
+            token_program: spl_token::id(),
+            target_account: pool.token_account(receiver),
+        },
+        jet_rewards::instruction::DistributionRelease {},
+    )
+}
+
+/// closes the distribution and its vault, sending the rent to `authority`
+pub fn distribution_close(authority: &Pubkey, seed: &str) -> Instruction {
+    let distribution = distribution(seed);
+    instruction(
+        jet_rewards::id(),
+        jet_rewards::accounts::DistributionClose {
+            distribution,
+            vault: reward_vault(&distribution),
+            receiver: *authority,
+            authority: *authority,
+            token_program: spl_token::id(),
+        },
+        jet_rewards::instruction::DistributionClose {},
+    )
+}
+
+/// the airdrop account itself has to be created beforehand, see [`create_airdrop_account`]
+pub fn airdrop_create(pool: &Pool, airdrop: &Pubkey, expire_at: i64) -> Instruction {
+    // flags are unused rn
+    airdrop_create_with(pool, airdrop, expire_at, "sdhdfshdfshdfhdfdfhhdf", 0)
+}
+
+pub fn airdrop_create_with(
+    pool: &Pool,
+    airdrop: &Pubkey,
+    expire_at: i64,
+    short_desc: &str,
+    flags: u64,
+) -> Instruction {
+    instruction(
+        jet_rewards::id(),
+        jet_rewards::accounts::AirdropCreate {
+            system_program: system_program::id(),
+            token_mint: pool.token_mint,
+            token_program: spl_token::id(),
+            rent: solana_program::sysvar::rent::id(),
+            airdrop: *airdrop,
+            authority: pool.authority,
+            reward_vault: reward_vault(airdrop),
+            payer: pool.authority,
+        },
+        jet_rewards::instruction::AirdropCreate {
+            params: jet_rewards::AirdropCreateParams {
+                expire_at,
+                stake_pool: pool.stake_pool(),
+                short_desc: short_desc.to_string(),
+                flags,
+            },
+        },
+    )
+}
+
+pub fn airdrop_account_size() -> usize {
+    8 + std::mem::size_of::<jet_rewards::state::Airdrop>()
+}
+
+pub fn create_airdrop_account(payer: &Pubkey, airdrop: &Pubkey, lamports: u64) -> Instruction {
+    solana_program::system_instruction::create_account(
+        payer,
+        airdrop,
+        lamports,
+        airdrop_account_size() as u64,
+        &jet_rewards::id(),
+    )
+}
+
+pub fn airdrop_add_recipients(
+    pool: &Pool,
+    airdrop: &Pubkey,
+    start_index: u64,
+    recipients: Vec<jet_rewards::AirdropRecipientParam>,
+) -> Instruction {
+    instruction(
+        jet_rewards::id(),
+        jet_rewards::accounts::AirdropAddRecipients {
+            airdrop: *airdrop,
+            authority: pool.authority,
+        },
+        jet_rewards::instruction::AirdropAddRecipients {
+            params: jet_rewards::AirdropAddRecipientsParams {
+                start_index,
+                recipients,
+            },
+        },
+    )
+}
+
+pub fn airdrop_finalize(pool: &Pool, airdrop: &Pubkey) -> Instruction {
+    instruction(
+        jet_rewards::id(),
+        jet_rewards::accounts::AirdropFinalize {
+            airdrop: *airdrop,
+            authority: pool.authority,
+            reward_vault: reward_vault(airdrop),
+        },
+        jet_rewards::instruction::AirdropFinalize {},
+    )
+}
+
+pub fn airdrop_claim(pool: &Pool, airdrop: &Pubkey, recipient: &Pubkey) -> Instruction {
+    instruction(
+        jet_rewards::id(),
+        jet_rewards::accounts::AirdropClaim {
+            airdrop: *airdrop,
+            reward_vault: reward_vault(airdrop),
+            recipient: *recipient,
+            // receiver is unused dunno why its there tbh
+            receiver: *recipient,
+            stake_pool: pool.stake_pool(),
+            stake_pool_vault: pool.vault(),
+            stake_account: pool.stake_account(recipient),
+            staking_program: jet_staking::id(),
+            token_program: spl_token::id(),
+        },
+        jet_rewards::instruction::AirdropClaim {},
+    )
+}
+
+/// closes the airdrop and its vault, unclaimed tokens go back to the pool authority
+pub fn airdrop_close(pool: &Pool, airdrop: &Pubkey) -> Instruction {
+    instruction(
+        jet_rewards::id(),
+        jet_rewards::accounts::AirdropClose {
+            airdrop: *airdrop,
+            reward_vault: reward_vault(airdrop),
+            authority: pool.authority,
+            receiver: pool.authority,
+            token_receiver: pool.token_account(&pool.authority),
+            token_program: spl_token::id(),
+        },
+        jet_rewards::instruction::AirdropClose {},
+    )
+}
@@ -0,0 +1,295 @@
+use super::Framework;
+use poc_framework_osec::{
+    solana_sdk::pubkey::Pubkey, solana_transaction_status::EncodedConfirmedTransaction,
+};
+use std::collections::HashMap;
+
+/// the lamport balance of one account before and after a transaction
+#[derive(Clone, Debug)]
+pub struct LamportChange {
+    pub account: Pubkey,
+    /// position in the transaction's account keys, 0 being the fee payer
+    pub index: usize,
+    pub signer: bool,
+    pub pre: u64,
+    pub post: u64,
+}
+
+impl LamportChange {
+    /// every account whose balance the transaction changed, in account key order
+    pub fn from_result(result: &EncodedConfirmedTransaction) -> Vec<LamportChange> {
+        let meta = match &result.transaction.meta {
+            Some(meta) => meta,
+            None => return vec![],
+        };
+        let transaction = match result.transaction.transaction.decode() {
+            Some(transaction) => transaction,
+            None => return vec![],
+        };
+
+        let signers = transaction.message.header.num_required_signatures as usize;
+        transaction
+            .message
+            .account_keys
+            .iter()
+            .zip(meta.pre_balances.iter().zip(&meta.post_balances))
+            .enumerate()
+            .filter(|(_, (_, (pre, post)))| pre != post)
+            .map(|(index, (account, (pre, post)))| LamportChange {
+                account: *account,
+                index,
+                signer: index < signers,
+                pre: *pre,
+                post: *post,
+            })
+            .collect()
+    }
+
+    pub fn delta(&self) -> i128 {
+        self.post as i128 - self.pre as i128
+    }
+
+    pub fn created(&self) -> bool {
+        self.pre == 0 && self.post > 0
+    }
+
+    pub fn closed(&self) -> bool {
+        self.pre > 0 && self.post == 0
+    }
+}
+
+/// an account emptied by a transaction and where its lamports went
+#[derive(Clone, Debug)]
+pub struct Closure {
+    pub helper: String,
+    pub account: Pubkey,
+    pub reclaimed: u64,
+    /// whoever funded the account when the framework saw it being created
+    pub payer: Option<Pubkey>,
+    /// every account gaining lamports in the closing transaction
+    pub receivers: Vec<(Pubkey, u64)>,
+}
+
+impl Closure {
+    /// the rent went somewhere, but not back to the account that paid it
+    pub fn misdirected(&self) -> bool {
+        match self.payer {
+            Some(payer) => !self
+                .receivers
+                .iter()
+                .any(|(receiver, _)| *receiver == payer),
+            None => false,
+        }
+    }
+}
+
+/// who paid for every account the framework saw created, and where it went when it was closed
+#[derive(Default)]
+pub struct RentLedger {
+    payers: HashMap<Pubkey, Pubkey>,
+    pub closures: Vec<Closure>,
+}
+
+impl RentLedger {
+    pub fn record(&mut self, helper: &str, result: &EncodedConfirmedTransaction) {
+        let fee = result
+            .transaction
+            .meta
+            .as_ref()
+            .map(|meta| meta.fee)
+            .unwrap_or(0);
+        self.record_changes(helper, fee, &LamportChange::from_result(result));
+    }
+
+    /// attributes every created account to the signer losing the most lamports. Only signers can
+    /// fund an account and a closing account funds nothing, so when rent refunded to the fee
+    /// payer hides what it paid, the fee payer is the funder
+    fn record_changes(&mut self, helper: &str, fee: u64, changes: &[LamportChange]) {
+        // leave the fee out so only transfers remain
+        let net = |change: &LamportChange| {
+            change.delta() + if change.index == 0 { fee as i128 } else { 0 }
+        };
+
+        let funders: Vec<&LamportChange> = changes
+            .iter()
+            .filter(|change| change.signer && !change.closed())
+            .collect();
+        let funder = funders
+            .iter()
+            .filter(|change| net(change) < 0)
+            .min_by_key(|change| net(change))
+            .or_else(|| funders.iter().find(|change| change.index == 0))
+            .map(|change| change.account);
+        let receivers: Vec<(Pubkey, u64)> = changes
+            .iter()
+            .filter(|change| !change.created() && net(change) > 0)
+            .map(|change| (change.account, net(change) as u64))
+            .collect();
+
+        for change in changes {
+            if change.created() {
+                if let Some(funder) = funder {
+                    self.payers.insert(change.account, funder);
+                }
+            } else if change.closed() {
+                self.closures.push(Closure {
+                    helper: helper.to_string(),
+                    account: change.account,
+                    reclaimed: change.pre,
+                    payer: self.payers.remove(&change.account),
+                    receivers: receivers.clone(),
+                });
+            }
+        }
+    }
+
+    pub fn reclaimed(&self) -> u64 {
+        self.closures.iter().map(|closure| closure.reclaimed).sum()
+    }
+
+    pub fn misdirected(&self) -> Vec<&Closure> {
+        self.closures
+            .iter()
+            .filter(|closure| closure.misdirected())
+            .collect()
+    }
+}
+
+impl Framework {
+    pub fn print_rent_report(&self) {
+        outln!(
+            "{} accounts closed, {} lamports reclaimed",
+            self.rent.closures.len(),
+            self.rent.reclaimed()
+        );
+        for closure in &self.rent.closures {
+            let receivers: Vec<String> = closure
+                .receivers
+                .iter()
+                .map(|(receiver, lamports)| format!("{} (+{})", self.label(receiver), lamports))
+                .collect();
+            outln!(
+                "  {}: {} closed, {} lamports to {}{}",
+                closure.helper,
+                self.label(&closure.account),
+                closure.reclaimed,
+                receivers.join(", "),
+                match closure.payer {
+                    Some(payer) if closure.misdirected() => {
+                        format!("  <-- rent was paid by {}", self.label(&payer))
+                    }
+                    _ => "".to_string(),
+                }
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEE: u64 = 5000;
+
+    fn change(account: Pubkey, index: usize, signer: bool, pre: u64, post: u64) -> LamportChange {
+        LamportChange {
+            account,
+            index,
+            signer,
+            pre,
+            post,
+        }
+    }
+
+    #[test]
+    fn the_signer_losing_the_most_funds_a_create() {
+        let (payer, creator, created) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut ledger = RentLedger::default();
+        // the payer only pays the fee, the creator pays the rent
+        ledger.record_changes(
+            "create",
+            FEE,
+            &[
+                change(payer, 0, true, 100000, 100000 - FEE),
+                change(creator, 1, true, 100000, 99000),
+                change(created, 2, false, 0, 1000),
+            ],
+        );
+
+        assert_eq!(ledger.payers.get(&created), Some(&creator));
+    }
+
+    #[test]
+    fn a_refunded_fee_payer_still_funds_its_create() {
+        let (payer, closed, created) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut ledger = RentLedger::default();
+        // the fee payer pays 1000 rent but gets 2000 back from the closed account, which loses
+        // more than anyone but can't have funded anything
+        ledger.record_changes(
+            "close_and_create",
+            FEE,
+            &[
+                change(payer, 0, true, 100000, 100000 + 2000 - 1000 - FEE),
+                change(closed, 1, false, 2000, 0),
+                change(created, 2, false, 0, 1000),
+            ],
+        );
+
+        assert_eq!(ledger.payers.get(&created), Some(&payer));
+        assert_eq!(ledger.closures.len(), 1);
+        assert_eq!(ledger.closures[0].receivers, vec![(payer, 1000)]);
+    }
+
+    #[test]
+    fn closures_paying_someone_else_are_misdirected() {
+        let (victim, attacker, account) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut ledger = RentLedger::default();
+        let create = |ledger: &mut RentLedger| {
+            ledger.record_changes(
+                "create",
+                FEE,
+                &[
+                    change(victim, 0, true, 100000, 99000 - FEE),
+                    change(account, 1, false, 0, 1000),
+                ],
+            )
+        };
+        let close = |ledger: &mut RentLedger, to: Pubkey| {
+            ledger.record_changes(
+                "close",
+                FEE,
+                &[
+                    change(attacker, 0, true, 100000, 100000 - FEE),
+                    change(account, 1, false, 1000, 0),
+                    change(to, 2, false, 100000, 101000),
+                ],
+            )
+        };
+
+        create(&mut ledger);
+        close(&mut ledger, victim);
+        assert!(!ledger.closures[0].misdirected());
+
+        create(&mut ledger);
+        close(&mut ledger, Pubkey::new_unique());
+        assert!(ledger.closures[1].misdirected());
+        assert_eq!(ledger.closures[1].payer, Some(victim));
+
+        // an account created before the framework was watching has no known payer
+        close(&mut ledger, Pubkey::new_unique());
+        assert!(!ledger.closures[2].misdirected());
+        assert_eq!(ledger.misdirected().len(), 1);
+    }
+}
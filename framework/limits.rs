@@ -0,0 +1,294 @@
+use super::{clone_keypair, ix, tx_builder::TxBuilder, Framework};
+use jet_rewards::AirdropRecipientParam;
+use poc_framework_osec::{
+    solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer},
+    Environment,
+};
+use std::error::Error;
+
+/// the largest size at which an operation still succeeds, and how it fails one step further
+#[derive(Clone, Debug)]
+pub struct Limit {
+    pub name: String,
+    pub max_ok: usize,
+    /// `None` if the search reached its upper bound without failing
+    pub failure: Option<String>,
+}
+
+impl Limit {
+    pub fn print(&self) {
+        match &self.failure {
+            Some(failure) => outln!(
+                "{}: at most {}, {} fails with: {}",
+                self.name,
+                self.max_ok,
+                self.max_ok + 1,
+                failure
+            ),
+            None => outln!(
+                "{}: {} still succeeds, the search bound",
+                self.name,
+                self.max_ok
+            ),
+        }
+    }
+}
+
+/// `count` distinct recipients in the ascending order airdrops expect
+pub fn dummy_recipients(count: usize) -> Vec<AirdropRecipientParam> {
+    let mut recipients: Vec<AirdropRecipientParam> = (0..count)
+        .map(|_| AirdropRecipientParam {
+            recipient: Pubkey::new_unique(),
+            amount: 1,
+        })
+        .collect();
+    recipients.sort_by_key(|param| param.recipient);
+
+    recipients
+}
+
+/// the search behind `Framework::find_limit`, `try_size` running one attempt from a clean state
+fn bisect_limit<F>(name: &str, max: usize, mut try_size: F) -> Limit
+where
+    F: FnMut(usize) -> Result<(), String>,
+{
+    // grow exponentially to bracket the limit, then bisect
+    let mut ok = 0;
+    let mut failure = None;
+    let mut fail = None;
+    let mut size = 1;
+    while size <= max {
+        match try_size(size) {
+            Ok(()) => ok = size,
+            Err(err) => {
+                fail = Some(size);
+                failure = Some(err);
+                break;
+            }
+        }
+        size *= 2;
+    }
+    let mut fail = match fail {
+        Some(fail) => fail,
+        None if ok == max => max + 1,
+        None => match try_size(max) {
+            Ok(()) => {
+                return Limit {
+                    name: name.to_string(),
+                    max_ok: max,
+                    failure: None,
+                }
+            }
+            Err(err) => {
+                failure = Some(err);
+                max
+            }
+        },
+    };
+    while fail - ok > 1 {
+        let mid = ok + (fail - ok) / 2;
+        match try_size(mid) {
+            Ok(()) => ok = mid,
+            Err(err) => {
+                fail = mid;
+                failure = Some(err);
+            }
+        }
+    }
+
+    Limit {
+        name: name.to_string(),
+        max_ok: ok,
+        failure,
+    }
+}
+
+impl Framework {
+    /// the largest `n` in `0..=max` for which `attempt(n)` succeeds, assuming larger sizes only
+    /// fail more. `accounts` are forked before the search and restored after every attempt, so
+    /// each one starts from the same state. Failed attempts are expected, so nothing is printed,
+    /// and the framework's own probes are left out of the profile, rent ledger and report
+    pub fn find_limit<F>(
+        &mut self,
+        name: &str,
+        max: usize,
+        accounts: &[Pubkey],
+        mut attempt: F,
+    ) -> Limit
+    where
+        F: FnMut(&mut Framework, usize) -> Result<(), String>,
+    {
+        let fork = self.fork(accounts);
+        let quiet = std::mem::replace(&mut self.quiet, true);
+        let limit = bisect_limit(name, max, |size| {
+            let result = attempt(self, size);
+            self.restore(&fork);
+            result
+        });
+        self.quiet = quiet;
+
+        limit
+    }
+
+    /// binary searches how long the list in a single instruction can get, `build` making the
+    /// instruction for a list of `n` entries. Fails either building the transaction, e.g. it
+    /// exceeds the packet size, or executing it
+    pub fn instruction_list_limit<F>(
+        &mut self,
+        name: &str,
+        max: usize,
+        accounts: &[Pubkey],
+        signer: &Keypair,
+        mut build: F,
+    ) -> Limit
+    where
+        F: FnMut(&Framework, usize) -> Instruction,
+    {
+        let signer = clone_keypair(signer);
+        self.find_limit(name, max, accounts, |test_env, size| {
+            let instruction = build(test_env, size);
+            let mut tx = TxBuilder::new();
+            tx.payer(&signer).instruction(instruction);
+            let transaction = tx.build(test_env).map_err(|err| err.to_string())?;
+            let outcome = test_env.execute_transaction_quietly(name, transaction);
+            match outcome.failure_mode() {
+                Some(failure) => Err(failure),
+                None => Ok(()),
+            }
+        })
+    }
+
+    /// recipients a single `airdrop_add_recipients` transaction can take
+    pub fn airdrop_recipients_per_tx(&mut self, airdrop: Pubkey, max: usize) -> Limit {
+        let pool_authority = clone_keypair(&self.pool_authority);
+        self.instruction_list_limit(
+            "recipients per airdrop_add_recipients",
+            max,
+            &[airdrop],
+            &pool_authority,
+            |test_env, size| {
+                ix::airdrop_add_recipients(&test_env.pool(), &airdrop, 0, dummy_recipients(size))
+            },
+        )
+    }
+
+    /// recipients the airdrop account can hold in total, filled in batches of `per_tx`. A batch
+    /// too large for a transaction fails the attempt rather than the search
+    pub fn airdrop_capacity(&mut self, airdrop: Pubkey, per_tx: usize, max: usize) -> Limit {
+        let data_len = self
+            .env
+            .get_account(airdrop)
+            .map(|account| account.data.len())
+            .unwrap_or(0);
+        let per_tx = per_tx.max(1);
+        let name = format!("recipients in a {} byte airdrop account", data_len);
+        let pool_authority = clone_keypair(&self.pool_authority);
+
+        self.find_limit(&name, max, &[airdrop], |test_env, size| {
+            let recipients = dummy_recipients(size);
+            for (idx, batch) in recipients.chunks(per_tx).enumerate() {
+                let mut tx = TxBuilder::new();
+                tx.payer(&pool_authority)
+                    .instruction(ix::airdrop_add_recipients(
+                        &test_env.pool(),
+                        &airdrop,
+                        (idx * per_tx) as u64,
+                        batch.to_vec(),
+                    ));
+                let transaction = tx
+                    .build(test_env)
+                    .map_err(|err| format!("batch {}: {}", idx, err))?;
+                let outcome =
+                    test_env.execute_transaction_quietly("airdrop_add_recipients", transaction);
+                if let Some(failure) = outcome.failure_mode() {
+                    return Err(format!("batch {}: {}", idx, failure));
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+/// how many recipients an `airdrop_add_recipients` transaction takes, and how many a fresh
+/// airdrop holds when filled in batches of that size. The search bound is one more than the
+/// recipient slots of the account, to see whether the program enforces them
+pub fn explore_limits() -> Result<Vec<Limit>, Box<dyn Error>> {
+    let mut test_env = Framework::new()?;
+    test_env.quiet = true;
+    test_env.init_stake_pool()?;
+    let airdrop = Keypair::new();
+    let expire_at = test_env.now() + 1000;
+    let create = test_env.create_airdrop(&airdrop, expire_at)?;
+    if let Some(err) = create.error() {
+        return Err(format!("creating the airdrop failed: {}", err).into());
+    }
+
+    let slots = test_env
+        .airdrop_state(&airdrop.pubkey())?
+        .target_info()
+        .recipients
+        .len();
+    let per_tx = test_env.airdrop_recipients_per_tx(airdrop.pubkey(), slots);
+    let capacity = test_env.airdrop_capacity(airdrop.pubkey(), per_tx.max_ok, slots + 1);
+
+    Ok(vec![per_tx, capacity])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the limit `bisect_limit` finds when sizes up to `threshold` succeed, and how many attempts
+    /// it took
+    fn search(threshold: usize, max: usize) -> (Limit, usize) {
+        let mut attempts = 0;
+        let limit = bisect_limit("test", max, |size| {
+            attempts += 1;
+            assert!(size <= max, "tried {} past the bound {}", size, max);
+            if size <= threshold {
+                Ok(())
+            } else {
+                Err(format!("{} is too many", size))
+            }
+        });
+
+        (limit, attempts)
+    }
+
+    #[test]
+    fn finds_a_threshold_in_between() {
+        for threshold in [1, 2, 3, 37, 64, 999] {
+            let (limit, attempts) = search(threshold, 1000);
+            assert_eq!(limit.max_ok, threshold);
+            assert_eq!(
+                limit.failure,
+                Some(format!("{} is too many", threshold + 1))
+            );
+            assert!(attempts <= 25, "{} attempts for {}", attempts, threshold);
+        }
+    }
+
+    #[test]
+    fn nothing_succeeding_is_a_limit_of_zero() {
+        let (limit, attempts) = search(0, 1000);
+        assert_eq!(limit.max_ok, 0);
+        assert_eq!(limit.failure, Some("1 is too many".to_string()));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn everything_succeeding_stops_at_the_bound() {
+        for max in [0, 1, 1000, 1024] {
+            let (limit, _) = search(usize::MAX, max);
+            assert_eq!(limit.max_ok, max);
+            assert_eq!(limit.failure, None);
+        }
+    }
+
+    #[test]
+    fn a_threshold_at_the_bound_is_found() {
+        let (limit, _) = search(999, 999);
+        assert_eq!(limit.max_ok, 999);
+        assert_eq!(limit.failure, None);
+    }
+}
@@ -0,0 +1,103 @@
+use super::{events::JetEvent, lamports::LamportChange, trace::Trace};
+use anchor_lang::Event;
+use poc_framework_osec::{
+    solana_sdk::transaction::Transaction, solana_transaction_status::EncodedConfirmedTransaction,
+};
+
+/// everything a helper knows about the transaction it just executed
+pub struct TxOutcome {
+    pub helper: String,
+    /// `None` when the helper ran inside `Framework::capture` and nothing was executed
+    pub result: Option<EncodedConfirmedTransaction>,
+    pub trace: Trace,
+    pub events: Vec<JetEvent>,
+    /// accounts whose lamport balance changed, the fee payer included
+    pub lamports: Vec<LamportChange>,
+}
+
+impl TxOutcome {
+    pub fn new(helper: &str, result: EncodedConfirmedTransaction) -> Self {
+        let trace = Trace::from_result(&result);
+        let events = trace
+            .walk()
+            .into_iter()
+            .flat_map(|frame| {
+                frame
+                    .data
+                    .iter()
+                    .map(move |data| JetEvent::decode(frame.program, data))
+            })
+            .collect();
+        let lamports = LamportChange::from_result(&result);
+
+        TxOutcome {
+            helper: helper.to_string(),
+            result: Some(result),
+            trace,
+            events,
+            lamports,
+        }
+    }
+
+    pub fn captured(helper: &str) -> Self {
+        TxOutcome {
+            helper: helper.to_string(),
+            result: None,
+            trace: Trace::default(),
+            events: vec![],
+            lamports: vec![],
+        }
+    }
+
+    /// the transaction as it was executed, signatures included
+    pub fn transaction(&self) -> Option<Transaction> {
+        self.result.as_ref()?.transaction.transaction.decode()
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.error().is_none()
+    }
+
+    pub fn error(&self) -> Option<String> {
+        let result = match &self.result {
+            Some(result) => result,
+            None => return Some("not executed".to_string()),
+        };
+        match &result.transaction.meta {
+            Some(meta) => meta.status.as_ref().err().map(|err| err.to_string()),
+            None => Some("missing transaction meta".to_string()),
+        }
+    }
+
+    /// the error along with the last log line of the failing program
+    pub fn failure_mode(&self) -> Option<String> {
+        let error = self.error()?;
+        let last_log = self
+            .trace
+            .failing_frame()
+            .and_then(|frame| frame.logs.last().cloned());
+
+        Some(match last_log {
+            Some(log) => format!("{} ({})", error, log),
+            None => error,
+        })
+    }
+
+    /// the first event of type `T` emitted anywhere in the transaction, including CPIs
+    pub fn event<T: Event>(&self) -> Option<T> {
+        self.events_of::<T>().into_iter().next()
+    }
+
+    pub fn events_of<T: Event>(&self) -> Vec<T> {
+        self.trace
+            .walk()
+            .into_iter()
+            .flat_map(|frame| frame.data.iter())
+            .filter_map(|data| super::events::decode_event::<T>(data))
+            .collect()
+    }
+
+    pub fn event_names(&self) -> Vec<String> {
+        self.events.iter().map(|event| event.name()).collect()
+    }
+}
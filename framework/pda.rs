@@ -0,0 +1,226 @@
+use super::program_name;
+use anchor_lang::solana_program::pubkey::{Pubkey, MAX_SEED_LEN};
+
+/// the jet programs keep the seed of a stake pool, award or distribution in a `[u8; 30]` to sign
+/// for the account later, see os-jet-adv-04
+pub const STORED_SEED_LEN: usize = 30;
+
+/// one component of a PDA derivation
+#[derive(Clone, Debug)]
+pub enum SeedPart {
+    Literal(&'static str),
+    /// a caller chosen string, like the stake pool or award seed
+    UserSeed,
+    /// a pubkey input, named by its role. Inputs with different roles are different accounts
+    Pubkey(&'static str),
+    /// the address of another framework PDA, named by the `derivation` of its scheme
+    Derived(&'static str),
+    U32(&'static str),
+}
+
+impl SeedPart {
+    /// the fixed size of this part, `None` for caller chosen strings
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            SeedPart::Literal(literal) => Some(literal.len()),
+            SeedPart::UserSeed => None,
+            SeedPart::Pubkey(_) | SeedPart::Derived(_) => Some(32),
+            SeedPart::U32(_) => Some(4),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            SeedPart::Literal(literal) => format!("{:?}", literal),
+            SeedPart::UserSeed => "seed".to_string(),
+            SeedPart::Pubkey(name) | SeedPart::Derived(name) => name.to_string(),
+            SeedPart::U32(name) => format!("{}.to_le_bytes()", name),
+        }
+    }
+}
+
+/// how the framework derives one kind of account
+#[derive(Clone, Debug)]
+pub struct PdaScheme {
+    /// the `Framework` / `ix` function doing the derivation
+    pub derivation: &'static str,
+    pub program: Pubkey,
+    pub seeds: Vec<SeedPart>,
+}
+
+impl PdaScheme {
+    pub fn takes_user_seed(&self) -> bool {
+        self.seeds
+            .iter()
+            .any(|part| matches!(part, SeedPart::UserSeed))
+    }
+
+    /// the bytes of the user seed the program keeps to re-derive the address when signing,
+    /// `None` if it doesn't sign for the account with the seed
+    pub fn stored_seed_len(&self) -> Option<usize> {
+        match self.derivation {
+            "stake_pool_pubkey"
+            | "stake_pool_vault_pubkey"
+            | "stake_vote_mint_pubkey"
+            | "collateral_mint"
+            | "award_pubkey"
+            | "distribution_pubkey" => Some(STORED_SEED_LEN),
+            _ => None,
+        }
+    }
+
+    /// the longest user seed the account stays usable with: `MAX_SEED_LEN`, as every part is
+    /// limited on its own, or less if the program keeps fewer bytes. `None` without a user seed
+    pub fn seed_limit(&self) -> Option<usize> {
+        if !self.takes_user_seed() {
+            return None;
+        }
+
+        Some(
+            self.stored_seed_len()
+                .map_or(MAX_SEED_LEN, |stored| stored.min(MAX_SEED_LEN)),
+        )
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "{}::{} = [{}]",
+            program_name(&self.program),
+            self.derivation,
+            self.seeds
+                .iter()
+                .map(|part| part.describe())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+/// every PDA the framework derives, across jet_auth, jet_staking and jet_rewards
+pub fn schemes() -> Vec<PdaScheme> {
+    vec![
+        PdaScheme {
+            derivation: "user_auth",
+            program: jet_auth::id(),
+            seeds: vec![SeedPart::Pubkey("user")],
+        },
+        PdaScheme {
+            derivation: "stake_pool_pubkey",
+            program: jet_staking::id(),
+            seeds: vec![SeedPart::UserSeed],
+        },
+        PdaScheme {
+            derivation: "stake_pool_vault_pubkey",
+            program: jet_staking::id(),
+            seeds: vec![SeedPart::UserSeed, SeedPart::Literal("vault")],
+        },
+        PdaScheme {
+            derivation: "stake_vote_mint_pubkey",
+            program: jet_staking::id(),
+            seeds: vec![SeedPart::UserSeed, SeedPart::Literal("vote-mint")],
+        },
+        PdaScheme {
+            derivation: "collateral_mint",
+            program: jet_staking::id(),
+            seeds: vec![SeedPart::UserSeed, SeedPart::Literal("collateral-mint")],
+        },
+        PdaScheme {
+            derivation: "stake_account_pubkey",
+            program: jet_staking::id(),
+            seeds: vec![SeedPart::Pubkey("stake_pool"), SeedPart::Pubkey("owner")],
+        },
+        PdaScheme {
+            derivation: "unbonding_account",
+            program: jet_staking::id(),
+            seeds: vec![
+                SeedPart::Pubkey("stake_account"),
+                SeedPart::U32("unbond_seed"),
+            ],
+        },
+        PdaScheme {
+            derivation: "award_pubkey",
+            program: jet_rewards::id(),
+            seeds: vec![SeedPart::Pubkey("stake_account"), SeedPart::UserSeed],
+        },
+        PdaScheme {
+            derivation: "distribution_pubkey",
+            program: jet_rewards::id(),
+            seeds: vec![SeedPart::UserSeed],
+        },
+        // the vault of every kind of reward has the same derivation, but takes a different
+        // kind of account, so each is a scheme of its own
+        PdaScheme {
+            derivation: "reward_vault_pubkey(award)",
+            program: jet_rewards::id(),
+            seeds: vec![
+                SeedPart::Derived("award_pubkey"),
+                SeedPart::Literal("vault"),
+            ],
+        },
+        PdaScheme {
+            derivation: "reward_vault_pubkey(distribution)",
+            program: jet_rewards::id(),
+            seeds: vec![
+                SeedPart::Derived("distribution_pubkey"),
+                SeedPart::Literal("vault"),
+            ],
+        },
+        PdaScheme {
+            derivation: "reward_vault_pubkey(airdrop)",
+            program: jet_rewards::id(),
+            seeds: vec![SeedPart::Pubkey("airdrop"), SeedPart::Literal("vault")],
+        },
+    ]
+}
+
+/// the static seed-length limits of every derivation taking a caller chosen seed
+pub fn print_seed_limits() {
+    outln!("caller seeds are limited to {} bytes each", MAX_SEED_LEN);
+    for scheme in schemes() {
+        outln!("  {}", scheme.describe());
+        for part in &scheme.seeds {
+            if let Some(len) = part.len() {
+                if len > MAX_SEED_LEN {
+                    outln!("    {} is {} bytes, never derivable", part.describe(), len);
+                }
+            }
+        }
+        if let Some(limit) = scheme.seed_limit() {
+            outln!(
+                "    derivable for seeds of 0..={} bytes, longer seeds are rejected client side",
+                MAX_SEED_LEN
+            );
+            if limit < MAX_SEED_LEN {
+                outln!(
+                    "    STUCK for seeds of {}..={} bytes, the program only keeps {} to sign with",
+                    limit + 1,
+                    MAX_SEED_LEN,
+                    limit
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheme(derivation: &str) -> PdaScheme {
+        schemes()
+            .into_iter()
+            .find(|scheme| scheme.derivation == derivation)
+            .unwrap()
+    }
+
+    #[test]
+    fn the_stored_seed_limits_awards_below_max_seed_len() {
+        // os-jet-adv-04: a 31 byte award seed derives fine but can't be signed for
+        let limit = scheme("award_pubkey").seed_limit().unwrap();
+        assert_eq!(limit, STORED_SEED_LEN);
+        assert!(31 > limit && 31 <= MAX_SEED_LEN);
+
+        assert_eq!(scheme("user_auth").seed_limit(), None);
+        assert_eq!(scheme("reward_vault_pubkey(airdrop)").seed_limit(), None);
+    }
+}
@@ -0,0 +1,224 @@
+use super::{helpers::scan_helpers, is_jet_program, trace::Trace};
+use poc_framework_osec::{
+    solana_sdk::signature::Keypair, solana_transaction_status::EncodedConfirmedTransaction,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    mem,
+};
+
+/// instructions using at least this percentage of the transaction's compute budget get flagged
+pub const NEAR_BUDGET_PERCENT: u64 = 90;
+
+struct Sample {
+    units: u64,
+    budget: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct InstructionStats {
+    pub instruction: String,
+    pub calls: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: u64,
+    pub budget: u64,
+    pub near_budget: bool,
+    /// the framework helpers this instruction was seen in
+    pub helpers: Vec<String>,
+}
+
+/// compute units used by every jet instruction, including ones reached through a CPI
+#[derive(Default)]
+pub struct ComputeProfile {
+    samples: BTreeMap<String, Vec<Sample>>,
+    helpers: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ComputeProfile {
+    pub fn record(&mut self, helper: &str, result: &EncodedConfirmedTransaction) {
+        self.record_trace(helper, &Trace::from_result(result));
+    }
+
+    /// a CPI logs what's left of the budget as its own, so every frame is measured against the
+    /// budget of the top-level instruction it runs under
+    pub fn record_trace(&mut self, helper: &str, trace: &Trace) {
+        for root in &trace.frames {
+            if root.budget == 0 {
+                continue;
+            }
+            for frame in root.walk() {
+                if !is_jet_program(&frame.program) || frame.budget == 0 {
+                    continue;
+                }
+                let name = frame.name();
+                self.samples.entry(name.clone()).or_default().push(Sample {
+                    units: frame.units,
+                    budget: root.budget,
+                });
+                self.helpers
+                    .entry(name)
+                    .or_default()
+                    .insert(helper.to_string());
+            }
+        }
+    }
+
+    /// adds the samples of another profile, e.g. one recorded on another thread
+    pub fn merge(&mut self, other: ComputeProfile) {
+        for (instruction, samples) in other.samples {
+            self.samples.entry(instruction).or_default().extend(samples);
+        }
+        for (instruction, helpers) in other.helpers {
+            self.helpers.entry(instruction).or_default().extend(helpers);
+        }
+    }
+
+    pub fn stats(&self) -> Vec<InstructionStats> {
+        self.samples
+            .iter()
+            .map(|(instruction, samples)| {
+                let min = samples.iter().map(|s| s.units).min().unwrap_or(0);
+                let max = samples.iter().map(|s| s.units).max().unwrap_or(0);
+                let total: u64 = samples.iter().map(|s| s.units).sum();
+                let budget = samples.iter().map(|s| s.budget).min().unwrap_or(0);
+                let near_budget = samples
+                    .iter()
+                    .any(|s| s.units * 100 >= s.budget * NEAR_BUDGET_PERCENT);
+
+                InstructionStats {
+                    instruction: instruction.clone(),
+                    calls: samples.len(),
+                    min,
+                    max,
+                    mean: total / samples.len() as u64,
+                    budget,
+                    near_budget,
+                    helpers: self.helpers[instruction].iter().cloned().collect(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn print_report(&self) {
+        outln!(
+            "{:<40} {:>6} {:>8} {:>8} {:>8} {:>8}",
+            "instruction",
+            "calls",
+            "min",
+            "max",
+            "mean",
+            "budget"
+        );
+        for stats in self.stats() {
+            outln!(
+                "{:<40} {:>6} {:>8} {:>8} {:>8} {:>8}{}",
+                stats.instruction,
+                stats.calls,
+                stats.min,
+                stats.max,
+                stats.mean,
+                stats.budget,
+                if stats.near_budget {
+                    "  <-- near compute budget"
+                } else {
+                    ""
+                }
+            );
+        }
+    }
+}
+
+/// executes every jet helper once, see [`super::helpers`], and profiles only those transactions
+pub fn profile_helpers(threads: usize) -> Result<ComputeProfile, Box<dyn Error>> {
+    let profiles = scan_helpers(threads, |test_env, tx| {
+        test_env.profile = ComputeProfile::default();
+        let signers: Vec<&Keypair> = tx.signers.iter().collect();
+        test_env.execute(&tx.helper, &tx.instructions, &tx.payer, &signers);
+        mem::take(&mut test_env.profile)
+    })?;
+
+    let mut profile = ComputeProfile::default();
+    for other in profiles {
+        profile.merge(other);
+    }
+
+    Ok(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a rewards instruction calling into staking, with the units each of them consumed
+    fn nested(outer: u64, inner: u64) -> Trace {
+        let rewards = jet_rewards::id();
+        let staking = jet_staking::id();
+        let logs = vec![
+            format!("Program {} invoke [1]", rewards),
+            "Program log: Instruction: AwardRelease".to_string(),
+            format!("Program {} invoke [2]", staking),
+            "Program log: Instruction: AddStake".to_string(),
+            format!(
+                "Program {} consumed {} of {} compute units",
+                staking,
+                inner,
+                200000 - (outer - inner)
+            ),
+            format!("Program {} success", staking),
+            format!(
+                "Program {} consumed {} of 200000 compute units",
+                rewards, outer
+            ),
+            format!("Program {} success", rewards),
+        ];
+
+        Trace::parse(&logs)
+    }
+
+    fn stats(profile: &ComputeProfile, instruction: &str) -> InstructionStats {
+        profile
+            .stats()
+            .into_iter()
+            .find(|stats| stats.instruction.ends_with(instruction))
+            .unwrap()
+    }
+
+    #[test]
+    fn cpis_are_measured_against_the_top_level_budget() {
+        // the CPI logs 170000 of 175000, 97% of what was left but only 85% of the transaction
+        let mut profile = ComputeProfile::default();
+        profile.record_trace("release_award", &nested(175000, 170000));
+
+        let inner = stats(&profile, "AddStake");
+        assert_eq!(inner.budget, 200000);
+        assert!(!inner.near_budget);
+        assert!(!stats(&profile, "AwardRelease").near_budget);
+    }
+
+    #[test]
+    fn instructions_near_the_top_level_budget_are_flagged() {
+        let mut profile = ComputeProfile::default();
+        profile.record_trace("release_award", &nested(195000, 185000));
+
+        assert!(stats(&profile, "AddStake").near_budget);
+        assert!(stats(&profile, "AwardRelease").near_budget);
+    }
+
+    #[test]
+    fn merged_profiles_keep_every_sample() {
+        let mut profile = ComputeProfile::default();
+        profile.record_trace("release_award", &nested(1000, 500));
+        let mut other = ComputeProfile::default();
+        other.record_trace("close_award", &nested(3000, 700));
+        profile.merge(other);
+
+        let inner = stats(&profile, "AddStake");
+        assert_eq!(
+            (inner.calls, inner.min, inner.max, inner.mean),
+            (2, 500, 700, 600)
+        );
+        assert_eq!(inner.helpers, vec!["close_award", "release_award"]);
+    }
+}
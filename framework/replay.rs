@@ -0,0 +1,319 @@
+use super::{
+    advance_blockhash, clone_keypair,
+    helpers::staked_user,
+    ix,
+    outcome::TxOutcome,
+    runner::{run_parallel, Job},
+    Framework,
+};
+use jet_rewards::AirdropRecipientParam;
+use poc_framework_osec::{
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signature},
+        signer::Signer,
+        transaction::Transaction,
+    },
+    Environment,
+};
+use std::error::Error;
+
+/// how a previously executed transaction is sent again
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Replay {
+    /// byte for byte, blockhash and signatures included
+    Exact,
+    /// the same message under a fresh blockhash, keeping the original signatures. The signatures
+    /// cover the blockhash, so this is expected to fail signature verification
+    StaleSignatures,
+    /// the same instructions signed again under a fresh blockhash
+    Resigned,
+}
+
+pub const REPLAYS: [Replay; 3] = [Replay::Exact, Replay::StaleSignatures, Replay::Resigned];
+
+impl Framework {
+    /// sends the transaction behind `outcome` again. `signers` are only needed for
+    /// `Replay::Resigned`, keypairs the transaction doesn't ask a signature from are ignored.
+    ///
+    /// The bank never checks signatures, so a transaction failing signature verification is
+    /// returned as an error here rather than executed, like a validator would drop it
+    pub fn replay(
+        &mut self,
+        outcome: &TxOutcome,
+        mode: Replay,
+        signers: &[&Keypair],
+    ) -> Result<TxOutcome, Box<dyn Error>> {
+        let transaction = self.replay_transaction(outcome, mode, signers)?;
+        let helper = format!("{} ({:?} replay)", outcome.helper, mode);
+
+        Ok(self.execute_transaction(&helper, transaction))
+    }
+
+    fn replay_transaction(
+        &mut self,
+        outcome: &TxOutcome,
+        mode: Replay,
+        signers: &[&Keypair],
+    ) -> Result<Transaction, Box<dyn Error>> {
+        let original = outcome
+            .transaction()
+            .ok_or_else(|| format!("{} has no executed transaction to replay", outcome.helper))?;
+
+        let transaction = match mode {
+            Replay::Exact => original,
+            Replay::StaleSignatures => {
+                let mut transaction = original;
+                transaction.message.recent_blockhash = advance_blockhash(&mut self.env);
+                transaction
+            }
+            Replay::Resigned => {
+                let required = original.message.header.num_required_signatures as usize;
+                let mut signers_used = vec![];
+                for pubkey in &original.message.account_keys[..required] {
+                    match signers.iter().find(|signer| signer.pubkey() == *pubkey) {
+                        Some(signer) => signers_used.push(*signer),
+                        None => return Err(format!("no keypair for signer {}", pubkey).into()),
+                    }
+                }
+                let mut transaction = Transaction::new_unsigned(original.message);
+                transaction.try_sign(&signers_used, advance_blockhash(&mut self.env))?;
+                transaction
+            }
+        };
+        if transaction.verify().is_err() {
+            return Err("signature verification failed".into());
+        }
+
+        Ok(transaction)
+    }
+
+    /// tries every kind of replay of `outcome` from the state it left behind, rolling back after
+    /// each one, and records what every accepted replay did to the token accounts it touches.
+    /// The replays are left out of the profile, rent ledger and report
+    pub fn check_replays(&mut self, outcome: &TxOutcome, signers: &[&Keypair]) -> Vec<ReplayCheck> {
+        let accounts = outcome
+            .transaction()
+            .map(|transaction| transaction.message.account_keys)
+            .unwrap_or_default();
+        let fork = self.fork(&accounts);
+
+        let mut checks = vec![];
+        for mode in REPLAYS {
+            let before: Vec<_> = accounts
+                .iter()
+                .map(|account| self.token_balance(account))
+                .collect();
+            let replayed = self
+                .replay_transaction(outcome, mode, signers)
+                .map(|transaction| {
+                    let helper = format!("{} ({:?} replay)", outcome.helper, mode);
+                    self.execute_transaction_quietly(&helper, transaction)
+                });
+            let token_changes = accounts
+                .iter()
+                .zip(before)
+                .filter_map(|(account, before)| {
+                    let change = self.token_balance(account).unwrap_or(0) as i128
+                        - before.unwrap_or(0) as i128;
+                    (change != 0).then(|| (*account, change))
+                })
+                .collect();
+            self.restore(&fork);
+
+            checks.push(ReplayCheck {
+                mode,
+                rejected: match replayed {
+                    Ok(replayed) => replayed.error(),
+                    Err(err) => Some(err.to_string()),
+                },
+                token_changes,
+            });
+        }
+
+        checks
+    }
+}
+
+pub struct ReplayCheck {
+    pub mode: Replay,
+    /// why the replay was refused, `None` if it went through
+    pub rejected: Option<String>,
+    /// token accounts of the transaction whose balance the replay changed
+    pub token_changes: Vec<(Pubkey, i128)>,
+}
+
+impl ReplayCheck {
+    /// tokens the replay moved on top of the original transaction
+    pub fn moved(&self) -> u64 {
+        self.token_changes
+            .iter()
+            .filter(|(_, change)| *change > 0)
+            .map(|(_, change)| *change as u64)
+            .sum()
+    }
+
+    /// accepted and paid out again
+    pub fn replayable(&self) -> bool {
+        self.rejected.is_none() && self.moved() > 0
+    }
+}
+
+pub struct ReplayCase {
+    pub name: &'static str,
+    pub original: Signature,
+    pub checks: Vec<ReplayCheck>,
+}
+
+impl ReplayCase {
+    /// every replay was refused or had no effect
+    pub fn idempotent(&self) -> bool {
+        !self.checks.iter().any(|check| check.replayable())
+    }
+}
+
+pub struct ReplayReport {
+    pub cases: Vec<ReplayCase>,
+}
+
+impl ReplayReport {
+    pub fn replayable(&self) -> Vec<&ReplayCase> {
+        self.cases
+            .iter()
+            .filter(|case| !case.idempotent())
+            .collect()
+    }
+
+    pub fn print(&self) {
+        for case in &self.cases {
+            outln!(
+                "{}: {}",
+                case.name,
+                if case.idempotent() {
+                    "idempotent"
+                } else {
+                    "REPLAYABLE"
+                }
+            );
+            for check in &case.checks {
+                match &check.rejected {
+                    Some(err) => outln!("  {:?} rejected: {}", check.mode, err),
+                    None if check.replayable() => outln!(
+                        "  {:?} accepted, moved {} more tokens",
+                        check.mode,
+                        check.moved()
+                    ),
+                    None => outln!("  {:?} accepted without effect", check.mode),
+                }
+            }
+        }
+    }
+}
+
+/// runs the operation under test once and returns its outcome along with the keypairs that
+/// signed it
+type Case = fn(&mut Framework) -> Result<(TxOutcome, Vec<Keypair>), Box<dyn Error>>;
+
+fn succeeded(outcome: TxOutcome) -> Result<TxOutcome, Box<dyn Error>> {
+    match outcome.error() {
+        Some(err) => Err(format!("{} failed before any replay: {}", outcome.helper, err).into()),
+        None => Ok(outcome),
+    }
+}
+
+fn cases() -> Vec<(&'static str, Case)> {
+    vec![
+        ("airdrop_claim", |fw| {
+            let recipient = staked_user(fw, 0)?;
+            let airdrop = Keypair::new();
+            let expire_at = fw.now() + 1000;
+            fw.create_airdrop(&airdrop, expire_at)?;
+            fw.env.mint_tokens(
+                fw.vault_token_mint.pubkey(),
+                &fw.vault_mint_authority,
+                ix::reward_vault(&airdrop.pubkey()),
+                1000,
+            );
+            let recipients = vec![AirdropRecipientParam {
+                recipient: recipient.pubkey(),
+                amount: 100,
+            }];
+            fw.airdrop_add_recipients(recipients, airdrop.pubkey(), 0)?;
+            fw.airdrop_finalize(airdrop.pubkey())?;
+            let claim = succeeded(fw.airdrop_claim(&recipient, airdrop.pubkey())?)?;
+            Ok((claim, vec![recipient]))
+        }),
+        ("release_award", |fw| {
+            let receiver = staked_user(fw, 0)?;
+            let creator = clone_keypair(&fw.victim);
+            fw.mint_vault_token(&creator, 1000)?;
+            let begin_at = fw.now();
+            fw.create_award(
+                &creator,
+                &receiver,
+                begin_at as u64,
+                begin_at as u64 + 1000,
+                1000,
+                "replay".to_string(),
+            )?;
+            fw.warp_to(begin_at + 500);
+            let release = succeeded(fw.release_award(&receiver, "replay".to_string())?)?;
+            let vault = ix::reward_vault(&ix::award(
+                &fw.pool().stake_account(&receiver.pubkey()),
+                "replay",
+            ));
+            match fw.token_balance(&vault) {
+                Some(500) => Ok((release, vec![receiver])),
+                left => Err(format!(
+                    "release_award left {:?} of 1000 tokens after half the window",
+                    left
+                )
+                .into()),
+            }
+        }),
+        ("withdraw_unbonded_stake", |fw| {
+            let user = staked_user(fw, 100)?;
+            fw.add_stake(&user, 100)?;
+            fw.unbond_stake_shares(&user, 0, 50)?;
+            let withdraw = succeeded(fw.withdraw_unbonded_stake(&user, 0)?)?;
+            Ok((withdraw, vec![user]))
+        }),
+    ]
+}
+
+/// executes `airdrop_claim`, `release_award` and `withdraw_unbonded_stake` once each in a fresh
+/// environment, then checks whether any kind of replay is accepted and pays out again
+pub fn run_replay_suite(threads: usize) -> Result<ReplayReport, Box<dyn Error>> {
+    let jobs: Vec<(String, Job<ReplayCase>)> = cases()
+        .into_iter()
+        .map(|(name, run)| {
+            let job: Job<ReplayCase> = Box::new(move || {
+                let mut test_env = Framework::new()?;
+                test_env.quiet = true;
+                let (outcome, signers) = run(&mut test_env)?;
+                let signers: Vec<&Keypair> = signers.iter().collect();
+                let original = outcome
+                    .transaction()
+                    .map(|transaction| transaction.signatures[0])
+                    .unwrap_or_default();
+                Ok(ReplayCase {
+                    name,
+                    original,
+                    checks: test_env.check_replays(&outcome, &signers),
+                })
+            });
+            (name.to_string(), job)
+        })
+        .collect();
+
+    let mut cases = vec![];
+    for run in run_parallel(jobs, threads) {
+        out!("{}", run.output);
+        match run.result {
+            Ok(case) => cases.push(case),
+            Err(err) => return Err(format!("{}: {}", run.name, err).into()),
+        }
+    }
+
+    Ok(ReplayReport { cases })
+}
@@ -0,0 +1,236 @@
+use super::{decode::decode, outcome::TxOutcome, program_name, Framework};
+use poc_framework_osec::{solana_sdk::signer::Signer, Environment};
+use serde::Serialize;
+use serde_json::Value;
+use std::{env, error::Error, fs, path::Path};
+
+/// directory every named report is written to when the framework is dropped, if set
+pub const REPORT_DIR_VAR: &str = "POC_REPORT_DIR";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Actor {
+    pub name: String,
+    pub pubkey: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct InstructionRecord {
+    pub program: String,
+    /// decoded from the anchor sighash of jet instructions, otherwise as logged by anchor
+    pub instruction: Option<String>,
+    /// the decoded arguments of jet instructions, `None` if they don't deserialize
+    pub args: Option<Value>,
+    pub accounts: Vec<String>,
+    /// the serialized instruction data, base58 encoded
+    pub data: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TxRecord {
+    pub helper: String,
+    pub instructions: Vec<InstructionRecord>,
+    pub signature: Option<String>,
+    pub status: &'static str,
+    /// the transaction error and the last log line of the program that failed
+    pub error: Option<String>,
+    /// consumed by the top level instructions
+    pub compute_units: u64,
+    pub logs: Vec<String>,
+}
+
+impl TxRecord {
+    pub fn from_outcome(outcome: &TxOutcome) -> Self {
+        let transaction = outcome.transaction();
+        let instructions = transaction
+            .iter()
+            .flat_map(|transaction| {
+                let keys = &transaction.message.account_keys;
+                transaction
+                    .message
+                    .instructions
+                    .iter()
+                    .enumerate()
+                    .map(move |(idx, ix)| (idx, keys, ix))
+            })
+            .map(|(idx, keys, ix)| {
+                let program = keys[ix.program_id_index as usize];
+                let decoded = decode(&program, &ix.data);
+                InstructionRecord {
+                    program: program_name(&program),
+                    instruction: match &decoded {
+                        Some(decoded) => Some(decoded.name.to_string()),
+                        None => outcome
+                            .trace
+                            .frames
+                            .get(idx)
+                            .and_then(|frame| frame.instruction.clone()),
+                    },
+                    args: decoded.and_then(|decoded| decoded.args),
+                    accounts: ix
+                        .accounts
+                        .iter()
+                        .map(|account| keys[*account as usize].to_string())
+                        .collect(),
+                    data: bs58::encode(&ix.data).into_string(),
+                }
+            })
+            .collect();
+        let logs = outcome
+            .result
+            .as_ref()
+            .and_then(|result| result.transaction.meta.as_ref())
+            .and_then(|meta| meta.log_messages.clone())
+            .unwrap_or_default();
+
+        TxRecord {
+            helper: outcome.helper.clone(),
+            instructions,
+            signature: transaction
+                .and_then(|transaction| transaction.signatures.first().map(|s| s.to_string())),
+            status: if outcome.is_ok() { "ok" } else { "failed" },
+            error: outcome.failure_mode(),
+            compute_units: outcome.trace.frames.iter().map(|frame| frame.units).sum(),
+            logs,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Balance {
+    pub account: String,
+    pub pubkey: String,
+    pub lamports: u64,
+    /// for token accounts
+    pub tokens: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BalanceSnapshot {
+    pub label: String,
+    pub balances: Vec<Balance>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Finding {
+    pub check: String,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Assertion {
+    pub name: String,
+    pub passed: bool,
+}
+
+/// everything a POC run did, serialized as JSON for reporting
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RunReport {
+    pub name: Option<String>,
+    pub actors: Vec<Actor>,
+    pub transactions: Vec<TxRecord>,
+    pub balances: Vec<BalanceSnapshot>,
+    pub findings: Vec<Finding>,
+    pub assertions: Vec<Assertion>,
+    /// set by `write_report`, so dropping the framework doesn't write it again
+    #[serde(skip)]
+    written: bool,
+}
+
+impl RunReport {
+    pub fn record(&mut self, outcome: &TxOutcome) {
+        self.transactions.push(TxRecord::from_outcome(outcome));
+    }
+
+    pub fn failed_assertions(&self) -> Vec<&Assertion> {
+        self.assertions.iter().filter(|a| !a.passed).collect()
+    }
+}
+
+impl Framework {
+    /// names the run and takes the "before" balance snapshot. Named runs are written to
+    /// `$POC_REPORT_DIR/<name>.json` when the framework is dropped, as long as the variable is
+    /// set and the report wasn't written already. That includes unwinding from a failed
+    /// `assert!(test_env.check(..))`, so the failed assertion makes it into the report
+    pub fn report_as(&mut self, name: &str) {
+        self.report.name = Some(name.to_string());
+        self.snapshot_balances("before");
+    }
+
+    /// records the lamports, and tokens where there are any, of every known account that exists
+    pub fn snapshot_balances(&mut self, label: &str) {
+        let balances = self
+            .known_accounts()
+            .into_iter()
+            .filter_map(|(account, pubkey)| {
+                let lamports = self.env.get_account(pubkey)?.lamports;
+                Some(Balance {
+                    account,
+                    pubkey: pubkey.to_string(),
+                    lamports,
+                    tokens: self.token_balance(&pubkey),
+                })
+            })
+            .collect();
+        self.report.balances.push(BalanceSnapshot {
+            label: label.to_string(),
+            balances,
+        });
+    }
+
+    /// records the violations found by an invariant check such as `check_award`
+    pub fn record_findings(&mut self, check: &str, findings: &[String]) {
+        self.report
+            .findings
+            .extend(findings.iter().map(|message| Finding {
+                check: check.to_string(),
+                message: message.clone(),
+            }));
+    }
+
+    /// records an assertion and returns its result, for `assert!(test_env.check(..))`
+    pub fn check(&mut self, name: &str, passed: bool) -> bool {
+        self.report.assertions.push(Assertion {
+            name: name.to_string(),
+            passed,
+        });
+
+        passed
+    }
+
+    /// takes the "after" balance snapshot and writes the report
+    pub fn write_report(&mut self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        self.snapshot_balances("after");
+        self.report.actors = [
+            ("attacker", &self.attacker),
+            ("victim", &self.victim),
+            ("pool authority", &self.pool_authority),
+            ("vault mint authority", &self.vault_mint_authority),
+        ]
+        .iter()
+        .map(|(name, keypair)| Actor {
+            name: name.to_string(),
+            pubkey: keypair.pubkey().to_string(),
+        })
+        .collect();
+        fs::write(path, serde_json::to_string_pretty(&self.report)?)?;
+        self.report.written = true;
+
+        Ok(())
+    }
+}
+
+impl Drop for Framework {
+    fn drop(&mut self) {
+        if self.report.written {
+            return;
+        }
+        let (name, dir) = match (self.report.name.clone(), env::var_os(REPORT_DIR_VAR)) {
+            (Some(name), Some(dir)) => (name, dir),
+            _ => return,
+        };
+        let path = Path::new(&dir).join(format!("{}.json", name));
+        if let Err(err) = self.write_report(&path) {
+            eprintln!("couldn't write report {}: {}", path.display(), err);
+        }
+    }
+}
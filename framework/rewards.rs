@@ -0,0 +1,271 @@
+use super::{ix, Framework};
+use anchor_lang::Discriminator;
+use bytemuck::{allocation::try_zeroed_box, bytes_of_mut};
+use jet_rewards::state::{Airdrop, Award, Distribution};
+use poc_framework_osec::{
+    solana_sdk::{program_pack::Pack, pubkey::Pubkey},
+    Environment,
+};
+use spl_token::state::Account as TokenAccount;
+use std::error::Error;
+
+impl Framework {
+    pub fn award_state(&self, receiver: &Pubkey, seed: &str) -> Result<Award, Box<dyn Error>> {
+        self.read_account(&ix::award(&self.pool().stake_account(receiver), seed))
+    }
+
+    pub fn distribution_state(&self, seed: &str) -> Result<Distribution, Box<dyn Error>> {
+        self.read_account(&ix::distribution(seed))
+    }
+
+    /// airdrops are zero copy, so they are copied straight from the account data. They are
+    /// too large for the stack of a runner thread, hence the box
+    pub fn airdrop_state(&self, airdrop: &Pubkey) -> Result<Box<Airdrop>, Box<dyn Error>> {
+        let account = self
+            .env
+            .get_account(*airdrop)
+            .ok_or_else(|| format!("airdrop {} doesn't exist", airdrop))?;
+        if account.data.len() < ix::airdrop_account_size()
+            || account.data[..8] != Airdrop::discriminator()
+        {
+            return Err(format!("{} is not an airdrop", airdrop).into());
+        }
+
+        let mut state: Box<Airdrop> =
+            try_zeroed_box().map_err(|_| "couldn't allocate an airdrop")?;
+        bytes_of_mut(&mut *state).copy_from_slice(&account.data[8..ix::airdrop_account_size()]);
+
+        Ok(state)
+    }
+
+    /// the balance of a token account, `None` if it doesn't exist
+    pub fn token_balance(&self, account: &Pubkey) -> Option<u64> {
+        let account = self.env.get_account(*account)?;
+        TokenAccount::unpack(&account.data)
+            .ok()
+            .map(|account| account.amount)
+    }
+
+    /// everything that should hold for an award whether it's open, revoked or closed. The
+    /// violations are returned and recorded in the report, as for the other checks
+    pub fn check_award(&mut self, receiver: &Pubkey, seed: &str) -> Vec<String> {
+        let award = ix::award(&self.pool().stake_account(receiver), seed);
+        let violations = match self.award_state(receiver, seed) {
+            Ok(state) => self.check_open_reward(
+                "award",
+                &award,
+                &state.vault,
+                state.target_amount,
+                state.distributed,
+            ),
+            Err(_) => self.check_closed_reward("award", &award),
+        };
+        self.record_findings("check_award", &violations);
+
+        violations
+    }
+
+    pub fn check_distribution(&mut self, seed: &str) -> Vec<String> {
+        let distribution = ix::distribution(seed);
+        let violations = match self.distribution_state(seed) {
+            Ok(state) => self.check_open_reward(
+                "distribution",
+                &distribution,
+                &state.vault,
+                state.target_amount,
+                state.distributed,
+            ),
+            Err(_) => self.check_closed_reward("distribution", &distribution),
+        };
+        self.record_findings("check_distribution", &violations);
+
+        violations
+    }
+
+    pub fn check_airdrop(&mut self, airdrop: &Pubkey) -> Vec<String> {
+        let vault = ix::reward_vault(airdrop);
+        let violations = if self.airdrop_state(airdrop).is_err() {
+            self.check_closed_reward("airdrop", airdrop)
+        } else if self.token_balance(&vault).is_none() {
+            vec![format!(
+                "airdrop {} is open but its vault {} is gone",
+                airdrop, vault
+            )]
+        } else {
+            vec![]
+        };
+        self.record_findings("check_airdrop", &violations);
+
+        violations
+    }
+
+    fn check_open_reward(
+        &self,
+        kind: &str,
+        account: &Pubkey,
+        vault: &Pubkey,
+        target_amount: u64,
+        distributed: u64,
+    ) -> Vec<String> {
+        let mut violations = vec![];
+        let expected_vault = ix::reward_vault(account);
+        if *vault != expected_vault {
+            violations.push(format!(
+                "{} {} points to vault {} instead of {}",
+                kind, account, vault, expected_vault
+            ));
+        }
+        if distributed > target_amount {
+            violations.push(format!(
+                "{} {} distributed {} of a target of {}",
+                kind, account, distributed, target_amount
+            ));
+        }
+        match self.token_balance(&expected_vault) {
+            None => violations.push(format!(
+                "{} {} is open but its vault {} is gone",
+                kind, account, expected_vault
+            )),
+            Some(balance) if balance < target_amount.saturating_sub(distributed) => violations
+                .push(format!(
+                    "{} {} still owes {} but its vault only holds {}",
+                    kind,
+                    account,
+                    target_amount - distributed,
+                    balance
+                )),
+            Some(_) => {}
+        }
+
+        violations
+    }
+
+    fn check_closed_reward(&self, kind: &str, account: &Pubkey) -> Vec<String> {
+        let vault = ix::reward_vault(account);
+        match self.token_balance(&vault) {
+            Some(balance) => vec![format!(
+                "{} {} is closed but its vault {} is still open holding {}",
+                kind, account, vault, balance
+            )],
+            None => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::{clone_keypair, helpers::staked_user, outcome::TxOutcome};
+    use poc_framework_osec::solana_sdk::{signature::Keypair, signer::Signer};
+
+    const SEED: &str = "recreate";
+    const AMOUNT: u64 = 1000;
+
+    /// a staked receiver and a creator holding enough tokens for two rewards
+    fn setup() -> (Framework, Keypair, Keypair) {
+        let mut test_env = Framework::new().unwrap();
+        test_env.quiet = true;
+        let receiver = staked_user(&mut test_env, 0).unwrap();
+        let creator = clone_keypair(&test_env.victim);
+        test_env.mint_vault_token(&creator, 2 * AMOUNT).unwrap();
+
+        (test_env, creator, receiver)
+    }
+
+    fn create_award(test_env: &mut Framework, creator: &Keypair, receiver: &Keypair) -> TxOutcome {
+        let begin_at = test_env.now() as u64;
+        test_env
+            .create_award(
+                creator,
+                receiver,
+                begin_at,
+                begin_at + 1000,
+                AMOUNT,
+                SEED.to_string(),
+            )
+            .unwrap()
+    }
+
+    fn create_distribution(
+        test_env: &mut Framework,
+        creator: &Keypair,
+        receiver: &Keypair,
+    ) -> TxOutcome {
+        let begin_at = test_env.now() as u64;
+        test_env
+            .create_distribution(
+                creator,
+                receiver,
+                begin_at,
+                begin_at + 1000,
+                AMOUNT,
+                SEED.to_string(),
+            )
+            .unwrap()
+    }
+
+    /// the violations `check` returned are exactly the ones the report recorded for it
+    fn assert_recorded(test_env: &Framework, check: &str, violations: &[String]) {
+        let recorded: Vec<&String> = test_env
+            .report
+            .findings
+            .iter()
+            .filter(|finding| finding.check == check)
+            .map(|finding| &finding.message)
+            .collect();
+        assert_eq!(recorded, violations.iter().collect::<Vec<_>>());
+    }
+
+    /// ends an award before anything was released, checks it, then creates it again at the same
+    /// address. Re-creating can only work if ending it really closed the account
+    fn end_award_early_and_recreate(end: fn(&mut Framework, &Keypair, &Keypair) -> TxOutcome) {
+        let (mut test_env, creator, receiver) = setup();
+        assert!(create_award(&mut test_env, &creator, &receiver).is_ok());
+
+        end(&mut test_env, &creator, &receiver);
+        let violations = test_env.check_award(&receiver.pubkey(), SEED);
+        assert_recorded(&test_env, "check_award", &violations);
+        assert_eq!(violations, Vec::<String>::new());
+        let closed = test_env.award_state(&receiver.pubkey(), SEED).is_err();
+
+        let recreate = create_award(&mut test_env, &creator, &receiver);
+        assert_eq!(recreate.is_ok(), closed);
+        assert_eq!(
+            test_env.check_award(&receiver.pubkey(), SEED),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn revoking_early_and_recreating_keeps_the_award_consistent() {
+        end_award_early_and_recreate(|fw, creator, receiver| {
+            fw.revoke_award(creator, receiver, SEED.to_string())
+                .unwrap()
+        });
+    }
+
+    #[test]
+    fn closing_early_and_recreating_keeps_the_award_consistent() {
+        end_award_early_and_recreate(|fw, creator, receiver| {
+            fw.close_award(creator, receiver, SEED.to_string()).unwrap()
+        });
+    }
+
+    #[test]
+    fn closing_early_and_recreating_keeps_the_distribution_consistent() {
+        let (mut test_env, creator, receiver) = setup();
+        assert!(create_distribution(&mut test_env, &creator, &receiver).is_ok());
+
+        test_env
+            .close_distribution(&creator, SEED.to_string())
+            .unwrap();
+        let violations = test_env.check_distribution(SEED);
+        assert_recorded(&test_env, "check_distribution", &violations);
+        assert_eq!(violations, Vec::<String>::new());
+        let closed = test_env.distribution_state(SEED).is_err();
+
+        let recreate = create_distribution(&mut test_env, &creator, &receiver);
+        assert_eq!(recreate.is_ok(), closed);
+        assert_eq!(test_env.check_distribution(SEED), Vec::<String>::new());
+    }
+}
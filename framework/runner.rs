@@ -0,0 +1,179 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    error::Error,
+    fmt::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+};
+
+thread_local! {
+    static CAPTURE: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// prints to stdout, or to the buffer of the enclosing `capture_output` on this thread.
+/// Used by the `out!` and `outln!` macros, which everything in the framework prints through
+pub fn write_output(args: fmt::Arguments) {
+    CAPTURE.with(|capture| match capture.borrow_mut().as_mut() {
+        Some(buffer) => buffer.write_fmt(args).unwrap(),
+        None => print!("{}", args),
+    });
+}
+
+/// runs `f` with the framework's output on this thread collected instead of printed. Only what
+/// goes through `out!` / `outln!` is collected, `println!` and the logs of the bank itself still
+/// go straight to stdout
+pub fn capture_output<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let outer = CAPTURE.with(|capture| capture.replace(Some(String::new())));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let output = CAPTURE
+        .with(|capture| capture.replace(outer))
+        .unwrap_or_default();
+
+    match result {
+        Ok(value) => (value, output),
+        Err(payload) => {
+            // keep what was printed before the panic
+            write_output(format_args!("{}", output));
+            panic::resume_unwind(payload)
+        }
+    }
+}
+
+/// a scenario run on the pool, with everything the framework printed while running it
+pub struct Run<T> {
+    pub name: String,
+    pub output: String,
+    /// the scenario's error or panic message
+    pub result: Result<T, String>,
+}
+
+impl<T> Run<T> {
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    pub fn print(&self) {
+        outln!("== {}", self.name);
+        out!("{}", self.output);
+        if let Err(err) = &self.result {
+            outln!("  error: {}", err);
+        }
+    }
+}
+
+pub type Job<T> = Box<dyn FnOnce() -> Result<T, Box<dyn Error>> + Send>;
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => format!("panicked: {}", message),
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => format!("panicked: {}", message),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
+
+/// the number of worker threads to use when the caller doesn't care
+pub fn default_threads() -> usize {
+    thread::available_parallelism()
+        .map(|threads| threads.get())
+        .unwrap_or(1)
+}
+
+/// runs independent scenarios on `threads` workers, each one usually building its own
+/// `Framework`. Output is captured per scenario so it doesn't interleave, callers print
+/// `Run::output` once the runs are back, and a scenario that fails or panics doesn't stop the
+/// others. Runs come back in the order they were given
+pub fn run_parallel<T: Send + 'static>(jobs: Vec<(String, Job<T>)>, threads: usize) -> Vec<Run<T>> {
+    let count = jobs.len();
+    let (job_tx, job_rx) = mpsc::channel();
+    for (idx, (name, job)) in jobs.into_iter().enumerate() {
+        job_tx.send((idx, name, job)).unwrap();
+    }
+    drop(job_tx);
+    let job_rx = Arc::new(Mutex::new(job_rx));
+
+    let (run_tx, run_rx) = mpsc::channel();
+    let workers: Vec<_> = (0..threads.max(1).min(count))
+        .map(|_| {
+            let job_rx = Arc::clone(&job_rx);
+            let run_tx = run_tx.clone();
+            thread::spawn(move || loop {
+                let next = job_rx.lock().unwrap().recv();
+                let (idx, name, job): (usize, String, Job<T>) = match next {
+                    Ok(next) => next,
+                    Err(_) => break,
+                };
+                let (result, output) =
+                    capture_output(|| match panic::catch_unwind(AssertUnwindSafe(job)) {
+                        Ok(result) => result.map_err(|err| err.to_string()),
+                        Err(payload) => Err(panic_message(payload)),
+                    });
+                run_tx
+                    .send((
+                        idx,
+                        Run {
+                            name,
+                            output,
+                            result,
+                        },
+                    ))
+                    .unwrap();
+            })
+        })
+        .collect();
+    drop(run_tx);
+
+    let mut runs: Vec<_> = run_rx.iter().collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    runs.sort_by_key(|(idx, _)| *idx);
+
+    runs.into_iter().map(|(_, run)| run).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn job(name: &'static str) -> (String, Job<()>) {
+        let job: Job<()> = Box::new(move || {
+            for line in 0..5 {
+                outln!("{} {}", name, line);
+                thread::sleep(Duration::from_millis(10));
+            }
+            Ok(())
+        });
+        (name.to_string(), job)
+    }
+
+    #[test]
+    fn parallel_output_does_not_interleave() {
+        let runs = run_parallel(vec![job("first"), job("second")], 2);
+
+        assert_eq!(runs.len(), 2);
+        for (run, name) in runs.iter().zip(["first", "second"]) {
+            assert_eq!(run.name, name);
+            assert!(run.is_ok());
+            let expected: String = (0..5).map(|line| format!("{} {}\n", name, line)).collect();
+            assert_eq!(run.output, expected);
+        }
+    }
+
+    #[test]
+    fn a_panicking_run_keeps_its_output() {
+        let panicking: Job<()> = Box::new(|| {
+            outln!("before");
+            panic!("boom")
+        });
+        let runs = run_parallel(vec![("panics".to_string(), panicking), job("fine")], 2);
+
+        assert_eq!(runs[0].output, "before\n");
+        assert_eq!(runs[0].result, Err("panicked: boom".to_string()));
+        assert!(runs[1].is_ok());
+    }
+}
@@ -0,0 +1,245 @@
+use super::{clone_keypair, outcome::TxOutcome, Framework};
+use anchor_lang::solana_program::pubkey::{Pubkey, MAX_SEED_LEN};
+use std::error::Error;
+
+/// ascii seeds of every length from 0 to 40 bytes, plus multibyte seeds around the 32 byte limit
+pub fn seed_corpus() -> Vec<String> {
+    let mut seeds: Vec<String> = (0..=40).map(|len| "a".repeat(len)).collect();
+    for c in ['é', '€', '😀'] {
+        for count in 1..=40 / c.len_utf8() {
+            let seed = c.to_string().repeat(count);
+            if seed.len() >= 24 {
+                seeds.push(seed);
+            }
+        }
+        // a multibyte char straddling the boundary
+        for len in 28..=33 {
+            seeds.push("a".repeat(len) + &c.to_string());
+        }
+    }
+    seeds.dedup();
+
+    seeds
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SeedFate {
+    /// the client can't derive an address for this seed at all
+    Underivable,
+    RejectedAtCreation(String),
+    Usable,
+    /// created fine but a later step fails, the interesting case
+    Stuck {
+        step: String,
+        error: String,
+    },
+}
+
+pub struct SeedResult {
+    pub family: &'static str,
+    pub seed: String,
+    pub fate: SeedFate,
+}
+
+pub struct SeedFuzzReport {
+    pub results: Vec<SeedResult>,
+}
+
+impl SeedFuzzReport {
+    pub fn stuck(&self) -> Vec<&SeedResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.fate, SeedFate::Stuck { .. }))
+            .collect()
+    }
+
+    pub fn print(&self) {
+        for family in ["stake_pool", "award", "distribution"] {
+            let results: Vec<&SeedResult> =
+                self.results.iter().filter(|r| r.family == family).collect();
+            let usable = results
+                .iter()
+                .filter(|r| r.fate == SeedFate::Usable)
+                .count();
+            outln!(
+                "{}: {} seeds tried, {} usable",
+                family,
+                results.len(),
+                usable
+            );
+            for result in results {
+                if let SeedFate::Stuck { step, error } = &result.fate {
+                    outln!(
+                        "  STUCK seed {:?} ({} chars, {} bytes) created but {} fails: {}",
+                        result.seed,
+                        result.seed.chars().count(),
+                        result.seed.len(),
+                        step,
+                        error
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn derivable(seeds: &[&[u8]], program: &Pubkey) -> bool {
+    seeds.iter().all(|seed| seed.len() <= MAX_SEED_LEN)
+        && Pubkey::try_find_program_address(seeds, program).is_some()
+}
+
+/// runs create and release for a stake pool, award and distribution with each seed, each in a
+/// fresh environment, and reports seeds that are accepted at creation but unusable afterwards
+pub fn fuzz_seeds(seeds: &[String]) -> Result<SeedFuzzReport, Box<dyn Error>> {
+    let mut results = vec![];
+    for seed in seeds {
+        results.push(SeedResult {
+            family: "stake_pool",
+            seed: seed.clone(),
+            fate: stake_pool_fate(seed)?,
+        });
+        let (award, distribution) = reward_fates(seed)?;
+        results.push(SeedResult {
+            family: "award",
+            seed: seed.clone(),
+            fate: award,
+        });
+        results.push(SeedResult {
+            family: "distribution",
+            seed: seed.clone(),
+            fate: distribution,
+        });
+    }
+
+    Ok(SeedFuzzReport { results })
+}
+
+/// the fate of the first failing outcome, `steps[0]` being the creation
+fn fate(steps: Vec<(&str, TxOutcome)>) -> SeedFate {
+    for (idx, (step, outcome)) in steps.into_iter().enumerate() {
+        if let Some(error) = outcome.error() {
+            return if idx == 0 {
+                SeedFate::RejectedAtCreation(error)
+            } else {
+                SeedFate::Stuck {
+                    step: step.to_string(),
+                    error,
+                }
+            };
+        }
+    }
+
+    SeedFate::Usable
+}
+
+fn stake_pool_fate(seed: &str) -> Result<SeedFate, Box<dyn Error>> {
+    let staking = jet_staking::id();
+    if !derivable(&[seed.as_bytes()], &staking)
+        || !derivable(&[seed.as_bytes(), b"vault"], &staking)
+        || !derivable(&[seed.as_bytes(), b"vote-mint"], &staking)
+        || !derivable(&[seed.as_bytes(), b"collateral-mint"], &staking)
+    {
+        return Ok(SeedFate::Underivable);
+    }
+
+    let mut test_env = Framework::new()?;
+    test_env.quiet = true;
+    test_env.seed = seed.to_string();
+    let user = clone_keypair(&test_env.victim);
+
+    let create = test_env.init_stake_pool()?;
+    if !create.is_ok() {
+        return Ok(fate(vec![("init_stake_pool", create)]));
+    }
+    test_env.create_user_auth(&user)?;
+    test_env.authenticate_user(&user)?;
+    test_env.mint_vault_token(&user, 100)?;
+
+    let steps = vec![
+        ("init_stake_pool", create),
+        ("init_stake_account", test_env.init_stake_account(&user)?),
+        ("add_stake", test_env.add_stake(&user, 100)?),
+        (
+            "unbond_stake_tokens",
+            test_env.unbond_stake_tokens(&user, 0, 100)?,
+        ),
+        (
+            "withdraw_unbonded_stake",
+            test_env.withdraw_unbonded_stake(&user, 0)?,
+        ),
+    ];
+
+    Ok(fate(steps))
+}
+
+fn reward_fates(seed: &str) -> Result<(SeedFate, SeedFate), Box<dyn Error>> {
+    let mut test_env = Framework::new()?;
+    test_env.quiet = true;
+    let creator = clone_keypair(&test_env.victim);
+    let receiver = clone_keypair(&test_env.attacker);
+
+    test_env.init_stake_pool()?;
+    test_env.create_user_auth(&receiver)?;
+    test_env.authenticate_user(&receiver)?;
+    test_env.init_stake_account(&receiver)?;
+    test_env.mint_vault_token(&creator, 2000)?;
+    test_env.mint_vault_token(&receiver, 0)?;
+
+    let stake_account = test_env.stake_account_pubkey(&receiver);
+    let award = if derivable(
+        &[stake_account.as_ref(), seed.as_bytes()],
+        &jet_rewards::id(),
+    ) {
+        let create = test_env.create_award(&creator, &receiver, 0, 1, 1000, seed.to_string())?;
+        let release = test_env.release_award(&receiver, seed.to_string())?;
+        fate(vec![("create_award", create), ("release_award", release)])
+    } else {
+        SeedFate::Underivable
+    };
+
+    let distribution = if derivable(&[seed.as_bytes()], &jet_rewards::id()) {
+        let create =
+            test_env.create_distribution(&creator, &receiver, 0, 1, 1000, seed.to_string())?;
+        let release = test_env.release_distribution(&receiver, seed.to_string())?;
+        fate(vec![
+            ("create_distribution", create),
+            ("release_distribution", release),
+        ])
+    } else {
+        SeedFate::Underivable
+    };
+
+    Ok((award, distribution))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_corpus_straddles_the_seed_limit() {
+        let corpus = seed_corpus();
+        for len in 0..=40 {
+            assert!(corpus.iter().any(|seed| seed.len() == len));
+        }
+        assert!(corpus
+            .iter()
+            .any(|seed| seed.chars().count() <= MAX_SEED_LEN && seed.len() > MAX_SEED_LEN));
+        let mut unique = corpus.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), corpus.len());
+    }
+
+    #[test]
+    fn seeds_over_max_seed_len_are_not_derivable() {
+        let program = jet_rewards::id();
+        let at_limit = "a".repeat(MAX_SEED_LEN);
+        let over_limit = "a".repeat(MAX_SEED_LEN + 1);
+
+        assert!(derivable(&[b""], &program));
+        assert!(derivable(&[at_limit.as_bytes(), b"vault"], &program));
+        assert!(!derivable(&[over_limit.as_bytes()], &program));
+        assert!(!derivable(&[b"vault", over_limit.as_bytes()], &program));
+    }
+}
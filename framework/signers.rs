@@ -0,0 +1,207 @@
+use super::{
+    clone_keypair, helpers::scan_helpers, is_jet_program, tx_builder::Captured, Framework,
+};
+use poc_framework_osec::{
+    solana_sdk::{
+        pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction::transfer,
+    },
+    Environment,
+};
+use std::{error::Error, fmt};
+
+#[derive(Clone, Debug)]
+pub enum SignerCheck {
+    /// the account stays the same but doesn't sign anymore
+    Dropped,
+    /// the account is swapped for an unrelated funded keypair, which does sign
+    Replaced(Pubkey),
+}
+
+impl fmt::Display for SignerCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignerCheck::Dropped => write!(f, "without its signature"),
+            SignerCheck::Replaced(pubkey) => write!(f, "replaced by unrelated signer {}", pubkey),
+        }
+    }
+}
+
+pub struct SignerAttempt {
+    pub instruction: usize,
+    pub account: usize,
+    pub signer: Pubkey,
+    pub signer_label: String,
+    pub check: SignerCheck,
+    /// `None` if the instruction still succeeded
+    pub error: Option<String>,
+}
+
+impl SignerAttempt {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+pub struct SignerReport {
+    pub helper: String,
+    /// set if the unmodified transaction already fails, in which case nothing was tried
+    pub baseline_error: Option<String>,
+    pub attempts: Vec<SignerAttempt>,
+}
+
+impl SignerReport {
+    pub fn succeeded(&self) -> Vec<&SignerAttempt> {
+        self.attempts.iter().filter(|a| a.succeeded()).collect()
+    }
+
+    pub fn print(&self) {
+        if let Some(err) = &self.baseline_error {
+            outln!(
+                "signer scan of {}: unmodified transaction fails: {}",
+                self.helper,
+                err
+            );
+            return;
+        }
+        outln!(
+            "signer scan of {}: {} attempts, {} still succeed",
+            self.helper,
+            self.attempts.len(),
+            self.succeeded().len()
+        );
+        for attempt in self.succeeded() {
+            outln!(
+                "  STILL SUCCEEDS instruction {} account {} ({}) {}",
+                attempt.instruction,
+                attempt.account,
+                attempt.signer_label,
+                attempt.check
+            );
+        }
+    }
+}
+
+impl Framework {
+    /// re-sends every jet instruction in `tx` with each of its signers dropped, and with each
+    /// signer account replaced by an unrelated keypair, reporting the attempts that still succeed
+    ///
+    /// the fee is paid by the unrelated keypair so dropping a signature isn't undone by the fee
+    /// payer signing, and the state is restored after every attempt
+    pub fn scan_signers(&mut self, tx: &Captured) -> SignerReport {
+        let stranger = Keypair::new();
+        let env_payer = self.env.payer();
+
+        let mut keys: Vec<Pubkey> = tx
+            .instructions
+            .iter()
+            .flat_map(|ix| ix.accounts.iter().map(|meta| meta.pubkey))
+            .collect();
+        keys.extend([tx.payer.pubkey(), stranger.pubkey(), env_payer.pubkey()]);
+        let fork = self.fork(&keys);
+
+        self.env.execute_as_transaction(
+            &[transfer(
+                &env_payer.pubkey(),
+                &stranger.pubkey(),
+                100000000000,
+            )],
+            &[&env_payer],
+        );
+        let funded = self.fork(&keys);
+
+        let mut signers: Vec<&Keypair> = tx.signers.iter().collect();
+        signers.push(&tx.payer);
+        signers.push(&stranger);
+        let stranger_payer = clone_keypair(&stranger);
+
+        let baseline =
+            self.execute_quietly(&tx.helper, &tx.instructions, &stranger_payer, &signers);
+        self.restore(&funded);
+
+        let mut report = SignerReport {
+            helper: tx.helper.clone(),
+            baseline_error: baseline.error(),
+            attempts: vec![],
+        };
+        if report.baseline_error.is_some() {
+            self.restore(&fork);
+            return report;
+        }
+
+        for (ix_idx, instruction) in tx.instructions.iter().enumerate() {
+            if !is_jet_program(&instruction.program_id) {
+                continue;
+            }
+            for (meta_idx, meta) in instruction.accounts.iter().enumerate() {
+                if !meta.is_signer {
+                    continue;
+                }
+
+                // a signature covers the whole transaction, so it has to go everywhere at once
+                let mut dropped = tx.instructions.clone();
+                for other in dropped.iter_mut().flat_map(|ix| ix.accounts.iter_mut()) {
+                    if other.pubkey == meta.pubkey {
+                        other.is_signer = false;
+                    }
+                }
+                let outcome = self.execute_quietly(&tx.helper, &dropped, &stranger_payer, &signers);
+                self.restore(&funded);
+                report.attempts.push(SignerAttempt {
+                    instruction: ix_idx,
+                    account: meta_idx,
+                    signer: meta.pubkey,
+                    signer_label: self.label(&meta.pubkey),
+                    check: SignerCheck::Dropped,
+                    error: outcome.error(),
+                });
+
+                let mut replaced = tx.instructions.clone();
+                replaced[ix_idx].accounts[meta_idx].pubkey = stranger.pubkey();
+                let outcome =
+                    self.execute_quietly(&tx.helper, &replaced, &stranger_payer, &signers);
+                self.restore(&funded);
+                report.attempts.push(SignerAttempt {
+                    instruction: ix_idx,
+                    account: meta_idx,
+                    signer: meta.pubkey,
+                    signer_label: self.label(&meta.pubkey),
+                    check: SignerCheck::Replaced(stranger.pubkey()),
+                    error: outcome.error(),
+                });
+            }
+        }
+        self.restore(&fork);
+
+        report
+    }
+}
+
+/// scans the transaction of every jet helper, see [`super::helpers`]
+pub fn scan_all_signers(threads: usize) -> Result<Vec<SignerReport>, Box<dyn Error>> {
+    scan_helpers(threads, Framework::scan_signers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::helpers::staked_user;
+
+    #[test]
+    fn add_stake_requires_the_owner() {
+        let mut test_env = Framework::new().unwrap();
+        test_env.quiet = true;
+        let user = staked_user(&mut test_env, 100).unwrap();
+
+        let captured = test_env.capture(|fw| fw.add_stake(&user, 100)).unwrap();
+        let report = test_env.scan_signers(&captured[0]);
+        assert_eq!(report.baseline_error, None);
+
+        let owner: Vec<&SignerAttempt> = report
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.signer == user.pubkey())
+            .collect();
+        assert!(!owner.is_empty());
+        assert!(owner.iter().all(|attempt| !attempt.succeeded()));
+    }
+}
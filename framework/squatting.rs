@@ -0,0 +1,325 @@
+use super::{clone_keypair, ix, outcome::TxOutcome, program_name, Framework};
+use poc_framework_osec::{
+    solana_sdk::{pubkey::Pubkey, signer::Signer, system_instruction::transfer},
+    Environment,
+};
+use std::{error::Error, fmt};
+
+/// the seed used for every seed-derived target, as if the attacker had guessed it
+const SEED: &str = "squat";
+
+/// lamports sent to a target when prefunding it
+const PREFUND_LAMPORTS: u64 = 1_000_000;
+
+type Step = fn(&mut Framework) -> Result<TxOutcome, Box<dyn Error>>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Squat {
+    /// lamports are sent to the address, leaving a system owned account behind
+    Prefund,
+    /// the attacker initializes the address first with their own parameters
+    PreCreate,
+}
+
+impl fmt::Display for Squat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Squat::Prefund => write!(f, "prefunded"),
+            Squat::PreCreate => write!(f, "pre-created by the attacker"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    /// the honest init still succeeds
+    Unaffected,
+    /// the honest init fails and the address holds a program account the attacker's transaction
+    /// created
+    Redirected(String),
+    /// the honest init fails, `escape` being the way around it if there is one
+    Blocked {
+        error: String,
+        escape: Option<&'static str>,
+    },
+    /// the attacker's own transaction fails, so this squat isn't possible
+    SquatRejected(String),
+    /// the honest flow fails even without an attacker, nothing to learn
+    HonestFlowBroken(String),
+}
+
+/// an init instruction writing to an address anyone can predict
+struct Target {
+    name: &'static str,
+    /// how the honest user can get around a squatted address, `None` if they can't
+    escape: Option<&'static str>,
+    setup: fn(&mut Framework) -> Result<(), Box<dyn Error>>,
+    address: fn(&Framework) -> Pubkey,
+    pre_create: Option<Step>,
+    honest: Step,
+}
+
+pub struct SquatResult {
+    pub target: &'static str,
+    pub address: Pubkey,
+    /// `None` for the baseline run without an attacker
+    pub squat: Option<Squat>,
+    pub verdict: Verdict,
+}
+
+pub struct SquatReport {
+    pub results: Vec<SquatResult>,
+}
+
+impl SquatReport {
+    /// squats leaving the honest user blocked or redirected
+    pub fn affected(&self) -> Vec<&SquatResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.verdict, Verdict::Redirected(_) | Verdict::Blocked { .. }))
+            .collect()
+    }
+
+    pub fn print(&self) {
+        for result in &self.results {
+            let verdict = match &result.verdict {
+                Verdict::Unaffected => "unaffected".to_string(),
+                Verdict::Redirected(owner) => {
+                    format!(
+                        "REDIRECTED, the address holds the attacker's {} account",
+                        owner
+                    )
+                }
+                Verdict::Blocked {
+                    error,
+                    escape: Some(escape),
+                } => format!("BLOCKED until the user would {}: {}", escape, error),
+                Verdict::Blocked {
+                    error,
+                    escape: None,
+                } => format!("PERMANENTLY BLOCKED: {}", error),
+                Verdict::SquatRejected(err) => format!("squat not possible: {}", err),
+                Verdict::HonestFlowBroken(err) => {
+                    format!("honest flow fails without an attacker: {}", err)
+                }
+            };
+            let squat = match result.squat {
+                Some(squat) => squat.to_string(),
+                None => "without an attacker".to_string(),
+            };
+            outln!(
+                "{} {} ({}): {}",
+                result.target,
+                squat,
+                result.address,
+                verdict
+            );
+        }
+    }
+}
+
+fn targets() -> Vec<Target> {
+    vec![
+        Target {
+            name: "user_auth",
+            escape: None,
+            setup: |_| Ok(()),
+            address: |fw| ix::user_auth(&fw.victim.pubkey()),
+            pre_create: None,
+            honest: |fw| {
+                let victim = clone_keypair(&fw.victim);
+                fw.create_user_auth(&victim)
+            },
+        },
+        Target {
+            name: "stake_pool",
+            escape: Some("pick another pool seed"),
+            setup: |_| Ok(()),
+            address: |fw| fw.pool().stake_pool(),
+            pre_create: Some(|fw| {
+                let attacker = clone_keypair(&fw.attacker);
+                let pool = ix::Pool {
+                    authority: attacker.pubkey(),
+                    ..fw.pool()
+                };
+                let instructions = vec![ix::init_pool(&pool, 0)];
+                Ok(fw.execute_quietly("init_stake_pool", &instructions, &attacker, &[&attacker]))
+            }),
+            honest: |fw| fw.init_stake_pool(),
+        },
+        Target {
+            name: "stake_account",
+            escape: None,
+            setup: |fw| {
+                let victim = clone_keypair(&fw.victim);
+                fw.init_stake_pool()?;
+                fw.create_user_auth(&victim)?;
+                fw.authenticate_user(&victim)?;
+                Ok(())
+            },
+            address: |fw| fw.pool().stake_account(&fw.victim.pubkey()),
+            pre_create: None,
+            honest: |fw| {
+                let victim = clone_keypair(&fw.victim);
+                fw.init_stake_account(&victim)
+            },
+        },
+        Target {
+            name: "unbonding_account",
+            escape: Some("pick another unbond seed"),
+            setup: |fw| {
+                let victim = clone_keypair(&fw.victim);
+                staker_setup(fw)?;
+                fw.add_stake(&victim, 100)?;
+                Ok(())
+            },
+            address: |fw| fw.pool().unbonding_account(&fw.victim.pubkey(), 0),
+            pre_create: None,
+            honest: |fw| {
+                let victim = clone_keypair(&fw.victim);
+                fw.unbond_stake_tokens(&victim, 0, 100)
+            },
+        },
+        Target {
+            name: "award",
+            escape: Some("pick another award seed"),
+            setup: staker_setup,
+            address: |fw| ix::award(&fw.pool().stake_account(&fw.victim.pubkey()), SEED),
+            pre_create: Some(|fw| {
+                let attacker = clone_keypair(&fw.attacker);
+                let victim = clone_keypair(&fw.victim);
+                fw.create_award(&attacker, &victim, 0, 1, 1, SEED.to_string())
+            }),
+            honest: |fw| {
+                let victim = clone_keypair(&fw.victim);
+                fw.create_award(&victim, &victim, 0, 1, 100, SEED.to_string())
+            },
+        },
+        Target {
+            name: "award vault",
+            escape: Some("pick another award seed"),
+            setup: staker_setup,
+            address: |fw| {
+                ix::reward_vault(&ix::award(
+                    &fw.pool().stake_account(&fw.victim.pubkey()),
+                    SEED,
+                ))
+            },
+            pre_create: None,
+            honest: |fw| {
+                let victim = clone_keypair(&fw.victim);
+                fw.create_award(&victim, &victim, 0, 1, 100, SEED.to_string())
+            },
+        },
+        Target {
+            name: "distribution",
+            escape: Some("pick another distribution seed"),
+            setup: staker_setup,
+            address: |_| ix::distribution(SEED),
+            pre_create: Some(|fw| {
+                let attacker = clone_keypair(&fw.attacker);
+                fw.create_distribution(&attacker, &attacker, 0, 1, 1, SEED.to_string())
+            }),
+            honest: |fw| {
+                let victim = clone_keypair(&fw.victim);
+                fw.create_distribution(&victim, &victim, 0, 1, 100, SEED.to_string())
+            },
+        },
+        Target {
+            name: "distribution vault",
+            escape: Some("pick another distribution seed"),
+            setup: staker_setup,
+            address: |_| ix::reward_vault(&ix::distribution(SEED)),
+            pre_create: None,
+            honest: |fw| {
+                let victim = clone_keypair(&fw.victim);
+                fw.create_distribution(&victim, &victim, 0, 1, 100, SEED.to_string())
+            },
+        },
+    ]
+}
+
+/// a stake pool with an authenticated victim and attacker who both hold vault tokens
+fn staker_setup(fw: &mut Framework) -> Result<(), Box<dyn Error>> {
+    fw.init_stake_pool()?;
+    for user in [clone_keypair(&fw.victim), clone_keypair(&fw.attacker)] {
+        fw.create_user_auth(&user)?;
+        fw.authenticate_user(&user)?;
+        fw.init_stake_account(&user)?;
+        fw.mint_vault_token(&user, 1000)?;
+    }
+
+    Ok(())
+}
+
+fn run(target: &Target, squat: Option<Squat>) -> Result<SquatResult, Box<dyn Error>> {
+    let mut test_env = Framework::new()?;
+    test_env.quiet = true;
+    (target.setup)(&mut test_env)?;
+    let address = (target.address)(&test_env);
+    let result = |verdict| SquatResult {
+        target: target.name,
+        address,
+        squat,
+        verdict,
+    };
+    let before = test_env.env.get_account(address);
+
+    let attack = match squat {
+        None => None,
+        Some(Squat::Prefund) => {
+            let attacker = clone_keypair(&test_env.attacker);
+            let instructions = vec![transfer(&attacker.pubkey(), &address, PREFUND_LAMPORTS)];
+            Some(test_env.execute_quietly("prefund", &instructions, &attacker, &[]))
+        }
+        Some(Squat::PreCreate) => match target.pre_create {
+            Some(pre_create) => Some(pre_create(&mut test_env)?),
+            None => None,
+        },
+    };
+    if let Some(error) = attack.and_then(|outcome| outcome.error()) {
+        return Ok(result(Verdict::SquatRejected(error)));
+    }
+
+    let squatted = test_env.env.get_account(address);
+
+    let honest = (target.honest)(&mut test_env)?;
+    let verdict = match honest.error() {
+        None => Verdict::Unaffected,
+        Some(error) if squat.is_none() => Verdict::HonestFlowBroken(error),
+        // a program account that was there before the squat isn't the attacker's doing
+        Some(error) => match squatted {
+            Some(account)
+                if account.owner != Pubkey::default() && Some(&account) != before.as_ref() =>
+            {
+                Verdict::Redirected(program_name(&account.owner))
+            }
+            _ => Verdict::Blocked {
+                error,
+                escape: target.escape,
+            },
+        },
+    };
+
+    Ok(result(verdict))
+}
+
+/// has the attacker prefund or pre-create every predictable address the framework initializes
+/// before the honest user's transaction, each in a fresh environment
+pub fn simulate_squatting() -> Result<SquatReport, Box<dyn Error>> {
+    let mut results = vec![];
+    for target in targets() {
+        let baseline = run(&target, None)?;
+        if let Verdict::HonestFlowBroken(_) = baseline.verdict {
+            results.push(baseline);
+            continue;
+        }
+
+        results.push(run(&target, Some(Squat::Prefund))?);
+        if target.pre_create.is_some() {
+            results.push(run(&target, Some(Squat::PreCreate))?);
+        }
+    }
+
+    Ok(SquatReport { results })
+}
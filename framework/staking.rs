@@ -0,0 +1,67 @@
+use super::Framework;
+use anchor_spl::associated_token::get_associated_token_address;
+use jet_staking::state::{StakeAccount, StakePool};
+use poc_framework_osec::solana_sdk::pubkey::Pubkey;
+use std::error::Error;
+
+impl Framework {
+    pub fn stake_pool_state(&self) -> Result<StakePool, Box<dyn Error>> {
+        self.read_account(&self.pool().stake_pool())
+    }
+
+    pub fn stake_account_state(&self, owner: &Pubkey) -> Result<StakeAccount, Box<dyn Error>> {
+        self.read_account(&self.pool().stake_account(owner))
+    }
+
+    /// the owner's vote tokens, `None` if they never minted any
+    pub fn vote_balance(&self, owner: &Pubkey) -> Option<u64> {
+        self.token_balance(&get_associated_token_address(
+            owner,
+            &self.pool().vote_mint(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::helpers::staked_user;
+    use poc_framework_osec::{solana_sdk::signer::Signer, Environment};
+
+    #[test]
+    fn burning_minted_votes_returns_to_zero() {
+        let mut test_env = Framework::new().unwrap();
+        test_env.quiet = true;
+        let user = staked_user(&mut test_env, 100).unwrap();
+        assert!(test_env.add_stake(&user, 100).unwrap().is_ok());
+        let vote_mint = test_env.stake_vote_mint_pubkey();
+        test_env
+            .env
+            .create_associated_token_account(&user, vote_mint);
+
+        assert!(test_env.mint_votes(&user, 100).unwrap().is_ok());
+        assert_eq!(test_env.vote_balance(&user.pubkey()), Some(100));
+        let state = test_env.stake_account_state(&user.pubkey()).unwrap();
+        assert_eq!(state.minted_votes, 100);
+
+        assert!(test_env.burn_votes(&user, Some(100)).unwrap().is_ok());
+        assert_eq!(test_env.vote_balance(&user.pubkey()), Some(0));
+        let state = test_env.stake_account_state(&user.pubkey()).unwrap();
+        assert_eq!(state.minted_votes, 0);
+    }
+
+    #[test]
+    fn a_closed_stake_account_comes_back_empty() {
+        let mut test_env = Framework::new().unwrap();
+        test_env.quiet = true;
+        let user = staked_user(&mut test_env, 0).unwrap();
+
+        assert!(test_env.close_stake_account(&user).unwrap().is_ok());
+        assert!(test_env.stake_account_state(&user.pubkey()).is_err());
+
+        assert!(test_env.init_stake_account(&user).unwrap().is_ok());
+        let state = test_env.stake_account_state(&user.pubkey()).unwrap();
+        assert_eq!(state.owner, user.pubkey());
+        assert_eq!(state.minted_votes, 0);
+    }
+}
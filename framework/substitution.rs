@@ -0,0 +1,349 @@
+use super::{
+    clone_keypair, helpers::scan_helpers, is_jet_program, tx_builder::Captured, Framework,
+};
+use poc_framework_osec::{
+    solana_sdk::{
+        account::Account, program_pack::Pack, pubkey::Pubkey, signature::Keypair, signer::Signer,
+        sysvar,
+    },
+    Environment,
+};
+use spl_token::{
+    solana_program::program_option::COption,
+    state::{Account as TokenAccount, Mint},
+};
+use std::{error::Error, fmt};
+
+/// what an account got replaced with
+#[derive(Clone, Debug)]
+pub enum Lookalike {
+    /// an existing account with the same owner and size, e.g. another user's stake account
+    Sibling(String),
+    /// a byte for byte copy at a new address, owned by the same program
+    Copy,
+    /// a byte for byte copy owned by a different program
+    ForeignOwner,
+    /// a token account of the same mint and balance, owned by the attacker
+    AttackerTokenAccount,
+    /// a token account of the same balance for a different mint, one with the supply and
+    /// decimals of the real one
+    WrongMintTokenAccount,
+    /// a mint with the same supply and decimals, controlled by the attacker
+    WrongMint,
+    /// an unused address, for accounts that don't exist yet
+    Fresh,
+}
+
+impl fmt::Display for Lookalike {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Lookalike::Sibling(name) => write!(f, "{}", name),
+            Lookalike::Copy => write!(f, "copy at another address"),
+            Lookalike::ForeignOwner => write!(f, "copy owned by another program"),
+            Lookalike::AttackerTokenAccount => write!(f, "attacker owned token account"),
+            Lookalike::WrongMintTokenAccount => write!(f, "token account of another mint"),
+            Lookalike::WrongMint => write!(f, "attacker controlled mint"),
+            Lookalike::Fresh => write!(f, "unused address"),
+        }
+    }
+}
+
+pub struct Substitution {
+    pub instruction: usize,
+    pub account: usize,
+    pub original: Pubkey,
+    pub original_label: String,
+    pub replacement: Pubkey,
+    pub lookalike: Lookalike,
+    /// `None` if the program accepted the substitution
+    pub error: Option<String>,
+}
+
+impl Substitution {
+    pub fn accepted(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+pub struct SubstitutionReport {
+    pub helper: String,
+    /// set if the unmodified transaction already fails, in which case nothing was substituted
+    pub baseline_error: Option<String>,
+    pub substitutions: Vec<Substitution>,
+}
+
+impl SubstitutionReport {
+    pub fn accepted(&self) -> Vec<&Substitution> {
+        self.substitutions.iter().filter(|s| s.accepted()).collect()
+    }
+
+    pub fn print(&self) {
+        if let Some(err) = &self.baseline_error {
+            outln!(
+                "substitution scan of {}: unmodified transaction fails: {}",
+                self.helper,
+                err
+            );
+            return;
+        }
+        outln!(
+            "substitution scan of {}: {} substitutions, {} accepted",
+            self.helper,
+            self.substitutions.len(),
+            self.accepted().len()
+        );
+        for substitution in self.accepted() {
+            outln!(
+                "  ACCEPTED instruction {} account {} ({}) replaced with {} ({})",
+                substitution.instruction,
+                substitution.account,
+                substitution.original_label,
+                substitution.lookalike,
+                substitution.replacement
+            );
+        }
+    }
+}
+
+impl Framework {
+    /// replaces every non-signer, non-program account of every jet instruction in `tx` with each of
+    /// its lookalikes in turn and reports which replacements the program accepts
+    ///
+    /// every attempt runs against the same state, which is restored afterwards
+    pub fn scan_substitutions(&mut self, tx: &Captured) -> SubstitutionReport {
+        let mut keys: Vec<Pubkey> = tx
+            .instructions
+            .iter()
+            .flat_map(|ix| ix.accounts.iter().map(|meta| meta.pubkey))
+            .collect();
+        keys.push(tx.payer.pubkey());
+        let fork = self.fork(&keys);
+
+        let payer = clone_keypair(&tx.payer);
+        let signers: Vec<&Keypair> = tx.signers.iter().collect();
+
+        let baseline = self.execute_quietly(&tx.helper, &tx.instructions, &payer, &signers);
+        self.restore(&fork);
+
+        let mut report = SubstitutionReport {
+            helper: tx.helper.clone(),
+            baseline_error: baseline.error(),
+            substitutions: vec![],
+        };
+        if report.baseline_error.is_some() {
+            return report;
+        }
+
+        for (ix_idx, instruction) in tx.instructions.iter().enumerate() {
+            if !is_jet_program(&instruction.program_id) {
+                continue;
+            }
+            for (meta_idx, meta) in instruction.accounts.iter().enumerate() {
+                if meta.is_signer {
+                    continue;
+                }
+                let account = self.env.get_account(meta.pubkey);
+                if let Some(account) = &account {
+                    if account.executable || account.owner == sysvar::id() {
+                        continue;
+                    }
+                }
+
+                for (lookalike, replacement, accounts) in
+                    self.lookalikes(&meta.pubkey, account.as_ref())
+                {
+                    let mut touched: Vec<Pubkey> = accounts.iter().map(|(key, _)| *key).collect();
+                    touched.push(replacement);
+                    let extra = self.fork(&touched);
+                    for (pubkey, account) in accounts {
+                        self.store_account(&pubkey, account);
+                    }
+
+                    let mut instructions = tx.instructions.clone();
+                    instructions[ix_idx].accounts[meta_idx].pubkey = replacement;
+                    let outcome = self.execute_quietly(&tx.helper, &instructions, &payer, &signers);
+
+                    self.restore(&fork);
+                    self.restore(&extra);
+
+                    report.substitutions.push(Substitution {
+                        instruction: ix_idx,
+                        account: meta_idx,
+                        original: meta.pubkey,
+                        original_label: self.label(&meta.pubkey),
+                        replacement,
+                        lookalike,
+                        error: outcome.error(),
+                    });
+                }
+            }
+        }
+
+        report
+    }
+
+    /// the replacements to try for `original`, with any accounts that have to be written first
+    fn lookalikes(
+        &self,
+        original: &Pubkey,
+        account: Option<&Account>,
+    ) -> Vec<(Lookalike, Pubkey, Vec<(Pubkey, Account)>)> {
+        let account = match account {
+            Some(account) => account,
+            None => return vec![(Lookalike::Fresh, Pubkey::new_unique(), vec![])],
+        };
+        let mut lookalikes = vec![];
+
+        for (name, sibling) in self.known_accounts() {
+            if sibling == *original {
+                continue;
+            }
+            if let Some(other) = self.env.get_account(sibling) {
+                if other.owner == account.owner && other.data.len() == account.data.len() {
+                    lookalikes.push((Lookalike::Sibling(name), sibling, vec![]));
+                }
+            }
+        }
+
+        let copy = Pubkey::new_unique();
+        lookalikes.push((Lookalike::Copy, copy, vec![(copy, account.clone())]));
+
+        let foreign = Pubkey::new_unique();
+        let foreign_account = Account {
+            owner: Pubkey::new_unique(),
+            ..account.clone()
+        };
+        lookalikes.push((
+            Lookalike::ForeignOwner,
+            foreign,
+            vec![(foreign, foreign_account)],
+        ));
+
+        if account.owner != spl_token::id() {
+            return lookalikes;
+        }
+
+        if let Ok(token) = TokenAccount::unpack(&account.data) {
+            let attacker_token = Pubkey::new_unique();
+            lookalikes.push((
+                Lookalike::AttackerTokenAccount,
+                attacker_token,
+                vec![(
+                    attacker_token,
+                    self.token_program_account(TokenAccount {
+                        owner: self.attacker.pubkey(),
+                        ..token
+                    }),
+                )],
+            ));
+
+            let wrong_mint = Pubkey::new_unique();
+            let wrong_token = Pubkey::new_unique();
+            let real_mint = self
+                .env
+                .get_account(token.mint)
+                .and_then(|mint| Mint::unpack(&mint.data).ok())
+                .unwrap_or(Mint {
+                    supply: token.amount,
+                    is_initialized: true,
+                    ..Mint::default()
+                });
+            let mint = Mint {
+                mint_authority: COption::Some(self.attacker.pubkey()),
+                freeze_authority: COption::None,
+                ..real_mint
+            };
+            lookalikes.push((
+                Lookalike::WrongMintTokenAccount,
+                wrong_token,
+                vec![
+                    (wrong_mint, self.token_program_account(mint)),
+                    (
+                        wrong_token,
+                        self.token_program_account(TokenAccount {
+                            mint: wrong_mint,
+                            ..token
+                        }),
+                    ),
+                ],
+            ));
+        }
+
+        if let Ok(mint) = Mint::unpack(&account.data) {
+            let wrong_mint = Pubkey::new_unique();
+            lookalikes.push((
+                Lookalike::WrongMint,
+                wrong_mint,
+                vec![(
+                    wrong_mint,
+                    self.token_program_account(Mint {
+                        mint_authority: COption::Some(self.attacker.pubkey()),
+                        ..mint
+                    }),
+                )],
+            ));
+        }
+
+        lookalikes
+    }
+
+    fn token_program_account<T: Pack>(&self, state: T) -> Account {
+        let mut data = vec![0; T::LEN];
+        T::pack(state, &mut data).unwrap();
+
+        Account {
+            lamports: self.env.get_rent_excemption(T::LEN),
+            data,
+            owner: spl_token::id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+}
+
+/// scans the transaction of every jet helper, see [`super::helpers`]
+pub fn scan_all_substitutions(threads: usize) -> Result<Vec<SubstitutionReport>, Box<dyn Error>> {
+    scan_helpers(threads, Framework::scan_substitutions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn authenticate_rejects_a_foreign_auth_account() {
+        let mut test_env = Framework::new().unwrap();
+        test_env.quiet = true;
+        let attacker = clone_keypair(&test_env.attacker);
+        let victim = clone_keypair(&test_env.victim);
+        test_env.create_user_auth(&attacker).unwrap();
+        test_env.create_user_auth(&victim).unwrap();
+
+        let captured = test_env
+            .capture(|fw| fw.authenticate_user(&attacker))
+            .unwrap();
+        let report = test_env.scan_substitutions(&captured[0]);
+        assert_eq!(report.baseline_error, None);
+
+        let foreign = report
+            .substitutions
+            .iter()
+            .find(|s| {
+                s.original_label == "attacker auth"
+                    && matches!(s.lookalike, Lookalike::ForeignOwner)
+            })
+            .unwrap();
+        assert!(!foreign.accepted());
+
+        // the authority isn't checked, see `Framework::authenticate_user`
+        let sibling = report
+            .substitutions
+            .iter()
+            .find(|s| {
+                s.original_label == "attacker"
+                    && matches!(&s.lookalike, Lookalike::Sibling(name) if name == "victim")
+            })
+            .unwrap();
+        assert!(sibling.accepted());
+    }
+}
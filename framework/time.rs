@@ -0,0 +1,99 @@
+use super::Framework;
+use anchor_lang::solana_program::{clock::Clock, sysvar};
+use poc_framework_osec::{
+    solana_sdk::{
+        account::{create_account_for_test, from_account},
+        genesis_config::GenesisConfig,
+        pubkey::Pubkey,
+    },
+    Environment,
+};
+use solana_runtime::bank::Bank;
+use std::{mem, sync::Arc};
+
+impl Framework {
+    pub fn clock(&self) -> Clock {
+        let account = self
+            .env
+            .get_account(sysvar::clock::id())
+            .expect("clock sysvar is missing");
+
+        from_account(&account).expect("clock sysvar is invalid")
+    }
+
+    /// the unix timestamp programs see through the clock sysvar
+    pub fn now(&self) -> i64 {
+        self.clock().unix_timestamp
+    }
+
+    /// moves the clock programs see to `clock.unix_timestamp`. Storing the sysvar alone isn't
+    /// enough, the bank hands programs the copy it cached when it was created, so this stores
+    /// it and moves on to a child bank one slot later, which inherits the timestamp.
+    ///
+    /// Only the timestamp carries over, slot and epoch follow the new bank, and the clock can't
+    /// be moved back before the timestamp the bank estimates for the slot
+    pub fn set_clock(&mut self, clock: &Clock) {
+        self.store_account(&sysvar::clock::id(), create_account_for_test(clock));
+
+        let bank = self.env.bank();
+        let parent = Arc::new(mem::replace(bank, Bank::new(&GenesisConfig::default())));
+        // roots the parent so warping again and again doesn't grow a chain of banks
+        parent.squash();
+        *bank = Bank::new_from_parent(&parent, &Pubkey::default(), parent.slot() + 1);
+    }
+
+    pub fn warp_to(&mut self, unix_timestamp: i64) {
+        let mut clock = self.clock();
+        clock.unix_timestamp = unix_timestamp;
+        self.set_clock(&clock);
+    }
+
+    pub fn advance_time(&mut self, seconds: i64) {
+        self.warp_to(self.now() + seconds);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::{clone_keypair, ix};
+    use poc_framework_osec::solana_sdk::signer::Signer;
+
+    #[test]
+    fn programs_see_the_warped_clock() {
+        let mut test_env = Framework::new().unwrap();
+        let creator = clone_keypair(&test_env.victim);
+        let receiver = clone_keypair(&test_env.attacker);
+        test_env.init_stake_pool().unwrap();
+        test_env.create_user_auth(&receiver).unwrap();
+        test_env.authenticate_user(&receiver).unwrap();
+        test_env.init_stake_account(&receiver).unwrap();
+        test_env.mint_vault_token(&creator, 1000).unwrap();
+
+        // an award vesting linearly over 1000 seconds has released half of it after 500
+        let begin_at = test_env.now();
+        let create = test_env
+            .create_award(
+                &creator,
+                &receiver,
+                begin_at as u64,
+                begin_at as u64 + 1000,
+                1000,
+                "clock".to_string(),
+            )
+            .unwrap();
+        assert!(create.is_ok());
+        test_env.warp_to(begin_at + 500);
+        assert_eq!(test_env.now(), begin_at + 500);
+        let release = test_env
+            .release_award(&receiver, "clock".to_string())
+            .unwrap();
+        assert!(release.is_ok());
+
+        let vault = ix::reward_vault(&ix::award(
+            &test_env.pool().stake_account(&receiver.pubkey()),
+            "clock",
+        ));
+        assert_eq!(test_env.token_balance(&vault), Some(500));
+    }
+}
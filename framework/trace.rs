@@ -0,0 +1,298 @@
+use super::program_name;
+use poc_framework_osec::{
+    solana_sdk::pubkey::Pubkey, solana_transaction_status::EncodedConfirmedTransaction,
+};
+use std::fmt;
+
+/// one program invocation, with the CPIs it made as children
+#[derive(Clone, Debug)]
+pub struct Frame {
+    pub program: Pubkey,
+    pub depth: usize,
+    pub instruction: Option<String>,
+    pub units: u64,
+    pub budget: u64,
+    pub logs: Vec<String>,
+    /// decoded `Program data:` payloads, i.e. anchor events
+    pub data: Vec<Vec<u8>>,
+    pub error: Option<String>,
+    pub children: Vec<Frame>,
+}
+
+impl Frame {
+    pub fn name(&self) -> String {
+        format!(
+            "{}::{}",
+            program_name(&self.program),
+            self.instruction.as_deref().unwrap_or("?")
+        )
+    }
+
+    /// this frame followed by every frame below it, depth first
+    pub fn walk(&self) -> Vec<&Frame> {
+        let mut frames = vec![self];
+        for child in &self.children {
+            frames.extend(child.walk());
+        }
+
+        frames
+    }
+
+    fn render(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let indent = "  ".repeat(self.depth.saturating_sub(1));
+        write!(f, "{}{}", indent, self.name())?;
+        if self.budget != 0 {
+            write!(f, " ({} of {} CU)", self.units, self.budget)?;
+        }
+        writeln!(f)?;
+        for line in &self.logs {
+            writeln!(f, "{}  | {}", indent, line)?;
+        }
+        for child in &self.children {
+            child.render(f)?;
+        }
+        if let Some(error) = &self.error {
+            writeln!(f, "{}  !! failed: {}", indent, error)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// the call tree of a transaction, rebuilt from its `invoke` / `success` / `failed` log lines
+#[derive(Clone, Debug, Default)]
+pub struct Trace {
+    pub frames: Vec<Frame>,
+    pub status: Option<String>,
+}
+
+impl Trace {
+    pub fn from_result(result: &EncodedConfirmedTransaction) -> Self {
+        let meta = match result.transaction.meta.as_ref() {
+            Some(meta) => meta,
+            None => return Trace::default(),
+        };
+        let mut trace = Trace::parse(meta.log_messages.as_deref().unwrap_or(&[]));
+        if let Err(err) = &meta.status {
+            trace.status = Some(err.to_string());
+        }
+
+        trace
+    }
+
+    pub fn parse(logs: &[String]) -> Self {
+        let mut roots: Vec<Frame> = vec![];
+        let mut stack: Vec<Frame> = vec![];
+
+        for line in logs {
+            if let Some(name) = line.strip_prefix("Program log: Instruction: ") {
+                if let Some(frame) = stack.last_mut() {
+                    frame.instruction.get_or_insert(name.trim().to_string());
+                }
+                continue;
+            }
+            if let Some(message) = line.strip_prefix("Program log: ") {
+                if let Some(frame) = stack.last_mut() {
+                    frame.logs.push(message.to_string());
+                }
+                continue;
+            }
+            if let Some(data) = line.strip_prefix("Program data: ") {
+                if let Some(frame) = stack.last_mut() {
+                    frame.data.push(
+                        data.split_whitespace()
+                            .filter_map(|chunk| base64::decode(chunk).ok())
+                            .flatten()
+                            .collect(),
+                    );
+                }
+                continue;
+            }
+            if line.starts_with("Program return: ") {
+                continue;
+            }
+
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let program = match words.get(1).map(|w| w.parse::<Pubkey>()) {
+                Some(Ok(program)) if words[0] == "Program" && words.len() >= 3 => program,
+                _ => {
+                    if let Some(frame) = stack.last_mut() {
+                        frame.logs.push(line.clone());
+                    }
+                    continue;
+                }
+            };
+
+            match words[2] {
+                "invoke" => {
+                    let depth = words
+                        .get(3)
+                        .and_then(|d| d.trim_matches(|c| c == '[' || c == ']').parse().ok())
+                        .unwrap_or(stack.len() + 1);
+                    stack.push(Frame {
+                        program,
+                        depth,
+                        instruction: None,
+                        units: 0,
+                        budget: 0,
+                        logs: vec![],
+                        data: vec![],
+                        error: None,
+                        children: vec![],
+                    });
+                }
+                // Program <id> consumed <units> of <budget> compute units
+                "consumed" => {
+                    if let Some(frame) = stack.last_mut() {
+                        frame.units = words.get(3).and_then(|u| u.parse().ok()).unwrap_or(0);
+                        frame.budget = words.get(5).and_then(|b| b.parse().ok()).unwrap_or(0);
+                    }
+                }
+                "success" | "failed:" | "failed" => {
+                    let mut frame = match stack.pop() {
+                        Some(frame) => frame,
+                        None => continue,
+                    };
+                    if words[2] != "success" {
+                        frame.error = Some(words[3..].join(" "));
+                    }
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(frame),
+                        None => roots.push(frame),
+                    }
+                }
+                _ => {
+                    if let Some(frame) = stack.last_mut() {
+                        frame.logs.push(line.clone());
+                    }
+                }
+            }
+        }
+
+        // frames that never finished, e.g. when the logs got truncated
+        while let Some(frame) = stack.pop() {
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(frame),
+                None => roots.push(frame),
+            }
+        }
+
+        Trace {
+            frames: roots,
+            status: None,
+        }
+    }
+
+    pub fn walk(&self) -> Vec<&Frame> {
+        self.frames.iter().flat_map(|frame| frame.walk()).collect()
+    }
+
+    /// the innermost frame that failed
+    pub fn failing_frame(&self) -> Option<&Frame> {
+        self.walk()
+            .into_iter()
+            .filter(|frame| frame.error.is_some())
+            .max_by_key(|frame| frame.depth)
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for frame in &self.frames {
+            frame.render(f)?;
+        }
+        if let Some(status) = &self.status {
+            writeln!(f, "transaction failed: {}", status)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logs(lines: &[&str]) -> Vec<String> {
+        let rewards = jet_rewards::id().to_string();
+        let staking = jet_staking::id().to_string();
+        lines
+            .iter()
+            .map(|line| {
+                line.replace("REWARDS", &rewards)
+                    .replace("STAKING", &staking)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nested_cpis_become_children() {
+        let trace = Trace::parse(&logs(&[
+            "Program REWARDS invoke [1]",
+            "Program log: Instruction: AwardRelease",
+            "Program STAKING invoke [2]",
+            "Program log: Instruction: AddStake",
+            "Program log: staked",
+            "Program STAKING consumed 5000 of 190000 compute units",
+            "Program STAKING success",
+            "Program REWARDS consumed 15000 of 200000 compute units",
+            "Program REWARDS success",
+        ]));
+
+        assert_eq!(trace.frames.len(), 1);
+        let outer = &trace.frames[0];
+        assert_eq!(outer.program, jet_rewards::id());
+        assert_eq!(outer.instruction.as_deref(), Some("AwardRelease"));
+        assert_eq!((outer.units, outer.budget), (15000, 200000));
+        assert_eq!(outer.children.len(), 1);
+
+        let inner = &outer.children[0];
+        assert_eq!(inner.program, jet_staking::id());
+        assert_eq!(inner.depth, 2);
+        assert_eq!(inner.instruction.as_deref(), Some("AddStake"));
+        assert_eq!(inner.logs, vec!["staked"]);
+        assert_eq!((inner.units, inner.budget), (5000, 190000));
+        assert_eq!(trace.walk().len(), 2);
+        assert!(trace.failing_frame().is_none());
+    }
+
+    #[test]
+    fn the_failing_frame_is_the_innermost_failure() {
+        let trace = Trace::parse(&logs(&[
+            "Program REWARDS invoke [1]",
+            "Program log: Instruction: AwardRelease",
+            "Program STAKING invoke [2]",
+            "Program log: Instruction: AddStake",
+            "Program STAKING consumed 5000 of 190000 compute units",
+            "Program STAKING failed: custom program error: 0x1",
+            "Program REWARDS consumed 15000 of 200000 compute units",
+            "Program REWARDS failed: custom program error: 0x1",
+        ]));
+
+        let failing = trace.failing_frame().unwrap();
+        assert_eq!(failing.program, jet_staking::id());
+        assert_eq!(failing.error.as_deref(), Some("custom program error: 0x1"));
+        assert!(trace.frames[0].error.is_some());
+    }
+
+    #[test]
+    fn truncated_logs_keep_the_unfinished_frames() {
+        let trace = Trace::parse(&logs(&[
+            "Program REWARDS invoke [1]",
+            "Program log: Instruction: AwardRelease",
+            "Program STAKING invoke [2]",
+            "Program log: Instruction: AddStake",
+            "Log truncated",
+        ]));
+
+        assert_eq!(trace.frames.len(), 1);
+        let outer = &trace.frames[0];
+        assert_eq!(outer.program, jet_rewards::id());
+        assert_eq!(outer.children.len(), 1);
+        let inner = &outer.children[0];
+        assert_eq!(inner.instruction.as_deref(), Some("AddStake"));
+        assert_eq!(inner.logs, vec!["Log truncated"]);
+        assert_eq!((inner.units, inner.budget), (0, 0));
+        assert!(trace.failing_frame().is_none());
+    }
+}
@@ -0,0 +1,285 @@
+use super::{clone_keypair, outcome::TxOutcome, Framework};
+use poc_framework_osec::{
+    solana_sdk::{
+        hash::Hash, instruction::Instruction, message::Message, packet::PACKET_DATA_SIZE,
+        pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction,
+    },
+    Environment,
+};
+use std::{error::Error, fmt};
+
+/// the runtime refuses to lock more accounts than this in a single transaction
+pub const MAX_TX_ACCOUNTS: usize = 64;
+
+/// the serialized size of a transaction carrying `message`, once signed
+pub fn transaction_size(message: &Message) -> usize {
+    // short_vec length prefix + signatures + message
+    1 + 64 * message.header.num_required_signatures as usize + message.serialize().len()
+}
+
+/// the instructions and signers of one transaction a helper would have sent
+pub struct Captured {
+    pub helper: String,
+    pub instructions: Vec<Instruction>,
+    pub payer: Keypair,
+    pub signers: Vec<Keypair>,
+}
+
+#[derive(Debug)]
+pub enum TxBuildError {
+    Empty,
+    NoPayer,
+    MissingSigner(Pubkey),
+    TooManyAccounts { accounts: usize, max: usize },
+    TooLarge { size: usize, max: usize },
+    Signing(String),
+}
+
+impl fmt::Display for TxBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TxBuildError::Empty => write!(f, "transaction has no instructions"),
+            TxBuildError::NoPayer => write!(f, "transaction has no fee payer"),
+            TxBuildError::MissingSigner(pubkey) => {
+                write!(f, "{} must sign but no keypair was given for it", pubkey)
+            }
+            TxBuildError::TooManyAccounts { accounts, max } => write!(
+                f,
+                "transaction references {} accounts, at most {} can be locked",
+                accounts, max
+            ),
+            TxBuildError::TooLarge { size, max } => write!(
+                f,
+                "transaction is {} bytes, larger than the {} byte packet limit",
+                size, max
+            ),
+            TxBuildError::Signing(err) => write!(f, "failed to sign transaction: {}", err),
+        }
+    }
+}
+
+impl Error for TxBuildError {}
+
+fn sign(
+    message: Message,
+    signers: &[&Keypair],
+    blockhash: Hash,
+) -> Result<Transaction, TxBuildError> {
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction
+        .try_sign(signers, blockhash)
+        .map_err(|err| TxBuildError::Signing(err.to_string()))?;
+
+    Ok(transaction)
+}
+
+/// collects instructions from several framework operations and sends them as one atomic transaction
+///
+/// ```ignore
+/// let mut tx = TxBuilder::new();
+/// tx.add(test_env.capture(|env| env.add_stake(&attacker, 100))?);
+/// tx.add(test_env.capture(|env| env.unbond_stake_tokens(&attacker, 0, 100))?);
+/// tx.add(test_env.capture(|env| env.withdraw_unbonded_stake(&attacker, 0))?);
+/// tx.send(&mut test_env, "stake_unbond_withdraw")?;
+/// ```
+#[derive(Default)]
+pub struct TxBuilder {
+    payer: Option<Keypair>,
+    instructions: Vec<Instruction>,
+    signers: Vec<Keypair>,
+}
+
+impl TxBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// defaults to the payer of the first captured transaction
+    pub fn payer(&mut self, payer: &Keypair) -> &mut Self {
+        self.payer = Some(clone_keypair(payer));
+        self.signer(payer)
+    }
+
+    pub fn add(&mut self, captured: Vec<Captured>) -> &mut Self {
+        for tx in captured {
+            if self.payer.is_none() {
+                self.payer = Some(clone_keypair(&tx.payer));
+            }
+            self.signer(&tx.payer);
+            for signer in &tx.signers {
+                self.signer(signer);
+            }
+            self.instructions.extend(tx.instructions);
+        }
+
+        self
+    }
+
+    pub fn instruction(&mut self, instruction: Instruction) -> &mut Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    pub fn signer(&mut self, signer: &Keypair) -> &mut Self {
+        if !self.signers.iter().any(|s| s.pubkey() == signer.pubkey()) {
+            self.signers.push(clone_keypair(signer));
+        }
+        self
+    }
+
+    pub fn build(&self, test_env: &mut Framework) -> Result<Transaction, TxBuildError> {
+        let (message, signers) = self.checked_message()?;
+
+        sign(message, &signers, test_env.next_blockhash())
+    }
+
+    /// the message and the keypairs signing it, after every check that doesn't need a blockhash
+    fn checked_message(&self) -> Result<(Message, Vec<&Keypair>), TxBuildError> {
+        if self.instructions.is_empty() {
+            return Err(TxBuildError::Empty);
+        }
+        let payer = self.payer.as_ref().ok_or(TxBuildError::NoPayer)?;
+
+        let message = Message::new(&self.instructions, Some(&payer.pubkey()));
+
+        if message.account_keys.len() > MAX_TX_ACCOUNTS {
+            return Err(TxBuildError::TooManyAccounts {
+                accounts: message.account_keys.len(),
+                max: MAX_TX_ACCOUNTS,
+            });
+        }
+
+        let required = &message.account_keys[..message.header.num_required_signatures as usize];
+        let mut signers: Vec<&Keypair> = vec![];
+        for pubkey in required {
+            match self.signers.iter().find(|s| s.pubkey() == *pubkey) {
+                Some(signer) => signers.push(signer),
+                None => return Err(TxBuildError::MissingSigner(*pubkey)),
+            }
+        }
+
+        let size = transaction_size(&message);
+        if size > PACKET_DATA_SIZE {
+            return Err(TxBuildError::TooLarge {
+                size,
+                max: PACKET_DATA_SIZE,
+            });
+        }
+
+        Ok((message, signers))
+    }
+
+    pub fn send(&self, test_env: &mut Framework, name: &str) -> Result<TxOutcome, TxBuildError> {
+        let transaction = self.build(test_env)?;
+
+        Ok(test_env.execute_transaction(name, transaction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poc_framework_osec::solana_sdk::instruction::AccountMeta;
+
+    fn instruction(accounts: Vec<AccountMeta>, data_len: usize) -> Instruction {
+        Instruction {
+            program_id: jet_staking::id(),
+            accounts,
+            data: vec![0; data_len],
+        }
+    }
+
+    fn readonly(count: usize) -> Vec<AccountMeta> {
+        (0..count)
+            .map(|_| AccountMeta::new_readonly(Pubkey::new_unique(), false))
+            .collect()
+    }
+
+    fn builder(instruction: Instruction) -> TxBuilder {
+        let mut tx = TxBuilder::new();
+        tx.payer(&Keypair::new()).instruction(instruction);
+        tx
+    }
+
+    #[test]
+    fn nothing_to_send_is_empty() {
+        let mut tx = TxBuilder::new();
+        tx.payer(&Keypair::new());
+        assert!(matches!(tx.checked_message(), Err(TxBuildError::Empty)));
+    }
+
+    #[test]
+    fn instructions_need_a_payer() {
+        let mut tx = TxBuilder::new();
+        tx.instruction(instruction(vec![], 0));
+        assert!(matches!(tx.checked_message(), Err(TxBuildError::NoPayer)));
+    }
+
+    #[test]
+    fn accounts_are_limited_to_max_tx_accounts() {
+        // the payer and the program take two of the keys
+        let at_limit = builder(instruction(readonly(MAX_TX_ACCOUNTS - 2), 0));
+        assert!(!matches!(
+            at_limit.checked_message(),
+            Err(TxBuildError::TooManyAccounts { .. })
+        ));
+
+        let over_limit = builder(instruction(readonly(MAX_TX_ACCOUNTS - 1), 0));
+        assert!(matches!(
+            over_limit.checked_message(),
+            Err(TxBuildError::TooManyAccounts {
+                accounts,
+                max: MAX_TX_ACCOUNTS,
+            }) if accounts == MAX_TX_ACCOUNTS + 1
+        ));
+    }
+
+    #[test]
+    fn every_required_signer_needs_a_keypair() {
+        let signer = Keypair::new();
+        let accounts = vec![AccountMeta::new_readonly(signer.pubkey(), true)];
+
+        let mut tx = builder(instruction(accounts, 0));
+        assert!(matches!(
+            tx.checked_message(),
+            Err(TxBuildError::MissingSigner(pubkey)) if pubkey == signer.pubkey()
+        ));
+
+        tx.signer(&signer);
+        assert!(tx.checked_message().is_ok());
+    }
+
+    #[test]
+    fn transactions_are_limited_to_a_packet() {
+        let size = |data_len| {
+            let tx = builder(instruction(vec![], data_len));
+            let message = Message::new(&tx.instructions, Some(&Keypair::new().pubkey()));
+            transaction_size(&message)
+        };
+        let fits = (0..PACKET_DATA_SIZE)
+            .find(|data_len| size(*data_len) == PACKET_DATA_SIZE)
+            .unwrap();
+
+        assert!(builder(instruction(vec![], fits)).checked_message().is_ok());
+        assert!(matches!(
+            builder(instruction(vec![], fits + 1)).checked_message(),
+            Err(TxBuildError::TooLarge {
+                size,
+                max: PACKET_DATA_SIZE,
+            }) if size == PACKET_DATA_SIZE + 1
+        ));
+    }
+
+    #[test]
+    fn keypairs_the_message_doesnt_ask_for_fail_signing() {
+        let tx = builder(instruction(vec![], 0));
+        let (message, mut signers) = tx.checked_message().unwrap();
+        let stranger = Keypair::new();
+        signers.push(&stranger);
+
+        assert!(matches!(
+            sign(message, &signers, Hash::default()),
+            Err(TxBuildError::Signing(_))
+        ));
+    }
+}
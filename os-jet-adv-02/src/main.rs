@@ -17,6 +17,7 @@ fn hedge_rate_increase() -> Result<(), Box<dyn Error>> {
     println!();
     println!("hedge rate increase");
     let mut test_env = Framework::new()?;
+    test_env.report_as("hedge_rate_increase");
     let attacker = clone_keypair(&test_env.attacker);
     let victim = clone_keypair(&test_env.victim);
 
@@ -68,6 +69,7 @@ fn hedge_rate_decrease() -> Result<(), Box<dyn Error>> {
     println!("hedge rate decrease");
 
     let mut test_env = Framework::new()?;
+    test_env.report_as("hedge_rate_decrease");
     let attacker = clone_keypair(&test_env.attacker);
     let victim = clone_keypair(&test_env.victim);
 
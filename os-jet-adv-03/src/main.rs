@@ -6,9 +6,10 @@ use jet_rewards::AirdropRecipientParam;
 use jet_staking::state::StakeAccount;
 use std::error::Error;
 
-use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
-
-use poc_framework_osec::Environment;
+use poc_framework_osec::{
+    solana_sdk::{signature::Keypair, signer::Signer},
+    Environment,
+};
 
 fn main() -> Result<(), Box<dyn Error>> {
     airdrop_double_claim()?;
@@ -21,6 +22,7 @@ fn airdrop_double_claim() -> Result<(), Box<dyn Error>> {
     println!("double claiming with airdrop..");
 
     let mut test_env = Framework::new()?;
+    test_env.report_as("airdrop_double_claim");
     let attacker = clone_keypair(&test_env.attacker);
 
     // setup
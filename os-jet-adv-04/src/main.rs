@@ -3,13 +3,12 @@ mod framework;
 use framework::{clone_keypair, Framework};
 use std::error::Error;
 
-use anchor_client::solana_sdk::account_info::IntoAccountInfo;
 use anchor_lang::{
     prelude::{Clock, SolanaSysvar},
     solana_program,
 };
 
-use poc_framework_osec::Environment;
+use poc_framework_osec::{solana_sdk::account_info::IntoAccountInfo, Environment};
 
 fn main() -> Result<(), Box<dyn Error>> {
     dos()?;
@@ -21,6 +20,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 fn dos() -> Result<(), Box<dyn Error>> {
     println!("demonstrating DOS with bad seed");
     let mut test_env = Framework::new()?;
+    test_env.report_as("dos_bad_seed");
 
     let attacker = clone_keypair(&test_env.attacker);
     let victim = clone_keypair(&test_env.victim);
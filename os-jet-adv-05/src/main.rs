@@ -13,6 +13,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 fn dos() -> Result<(), Box<dyn Error>> {
     println!("demonstrating distribution seed collision");
     let mut test_env = Framework::new()?;
+    test_env.report_as("distribution_seed_collision");
     let attacker = clone_keypair(&test_env.attacker);
     let victim = clone_keypair(&test_env.victim);
 
@@ -5,8 +5,9 @@ SCRIPT_DIR=$ROOT/$1
 
 if [ ! -f "$SCRIPT_DIR/Cargo.toml" ]
 then
-  echo "usage: $0 <dirname>"
+  echo "usage: $0 <dirname> [args]"
   exit 1
 fi
+shift
 
-$ROOT/build.sh $SCRIPT_DIR && cd $SCRIPT_DIR && cargo r --release | grep -v "writing bytes"
+$ROOT/build.sh $SCRIPT_DIR && cd $SCRIPT_DIR && cargo r --release -- "$@" | grep -v "writing bytes"
@@ -0,0 +1 @@
+../../framework/framework.rs
\ No newline at end of file
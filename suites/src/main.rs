@@ -0,0 +1,166 @@
+mod framework;
+use framework::{
+    airdrop_suite::run_airdrop_suite,
+    collision::print_collisions,
+    limits::explore_limits,
+    pda::print_seed_limits,
+    profile::profile_helpers,
+    replay::run_replay_suite,
+    runner::default_threads,
+    seed_fuzz::{fuzz_seeds, seed_corpus},
+    signers::scan_all_signers,
+    squatting::simulate_squatting,
+    substitution::scan_all_substitutions,
+    vesting::{verify_vesting, RewardKind, VestingConfig},
+    Framework,
+};
+use std::{env, error::Error};
+
+use poc_framework_osec::solana_sdk::{signature::Keypair, signer::Signer};
+
+const USAGE: &str = "usage: suites <command>
+
+commands:
+  airdrop
+  replay
+  vesting
+  squatting
+  collisions
+  seeds
+  limits
+  substitution
+  signers
+  profile
+  load <recipients.csv>";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        ["airdrop"] => airdrop(),
+        ["replay"] => replay(),
+        ["vesting"] => vesting(),
+        ["squatting"] => squatting(),
+        ["collisions"] => {
+            print_collisions();
+            Ok(())
+        }
+        ["seeds"] => seeds(),
+        ["limits"] => {
+            for limit in explore_limits()? {
+                limit.print();
+            }
+            Ok(())
+        }
+        ["substitution"] => substitution(),
+        ["signers"] => signers(),
+        ["profile"] => {
+            profile_helpers(default_threads())?.print_report();
+            Ok(())
+        }
+        ["load", path] => load(path),
+        _ => Err(USAGE.into()),
+    }
+}
+
+fn airdrop() -> Result<(), Box<dyn Error>> {
+    let report = run_airdrop_suite(default_threads())?;
+    report.print();
+
+    match report.failed().len() {
+        0 => Ok(()),
+        failed => Err(format!("{} airdrop scenarios failed", failed).into()),
+    }
+}
+
+fn replay() -> Result<(), Box<dyn Error>> {
+    let report = run_replay_suite(default_threads())?;
+    report.print();
+
+    match report.replayable().len() {
+        0 => Ok(()),
+        replayable => Err(format!("{} instructions are replayable", replayable).into()),
+    }
+}
+
+fn vesting() -> Result<(), Box<dyn Error>> {
+    let config = VestingConfig::default();
+    let mut early = 0;
+    for kind in [RewardKind::Award, RewardKind::Distribution] {
+        let report = verify_vesting(kind, &config)?;
+        report.print();
+        early += report.early().len();
+    }
+
+    match early {
+        0 => Ok(()),
+        early => Err(format!("{} releases paid out early", early).into()),
+    }
+}
+
+fn squatting() -> Result<(), Box<dyn Error>> {
+    let report = simulate_squatting()?;
+    report.print();
+
+    match report.affected().len() {
+        0 => Ok(()),
+        affected => Err(format!("{} addresses can be squatted", affected).into()),
+    }
+}
+
+fn seeds() -> Result<(), Box<dyn Error>> {
+    print_seed_limits();
+    let report = fuzz_seeds(&seed_corpus())?;
+    report.print();
+
+    match report.stuck().len() {
+        0 => Ok(()),
+        stuck => Err(format!("{} seeds leave funds stuck", stuck).into()),
+    }
+}
+
+fn substitution() -> Result<(), Box<dyn Error>> {
+    let mut accepted = 0;
+    for report in scan_all_substitutions(default_threads())? {
+        report.print();
+        accepted += report.accepted().len();
+    }
+
+    match accepted {
+        0 => Ok(()),
+        accepted => Err(format!("{} account substitutions accepted", accepted).into()),
+    }
+}
+
+fn signers() -> Result<(), Box<dyn Error>> {
+    let mut succeeded = 0;
+    for report in scan_all_signers(default_threads())? {
+        report.print();
+        succeeded += report.succeeded().len();
+    }
+
+    match succeeded {
+        0 => Ok(()),
+        succeeded => Err(format!("{} signer checks missing", succeeded).into()),
+    }
+}
+
+/// loads the recipients of a CSV file into a fresh airdrop
+fn load(path: &str) -> Result<(), Box<dyn Error>> {
+    let mut test_env = Framework::new()?;
+    test_env.quiet = true;
+    test_env.init_stake_pool()?;
+    let airdrop = Keypair::new();
+    let expire_at = test_env.now() + 1000;
+    test_env.create_airdrop(&airdrop, expire_at)?;
+
+    let load = test_env.airdrop_load_csv(airdrop.pubkey(), path)?;
+    load.print();
+
+    if load.is_ok() {
+        Ok(())
+    } else {
+        Err(format!("loading {} failed", path).into())
+    }
+}